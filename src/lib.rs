@@ -1,15 +1,28 @@
-use std::fmt::Display;
+use std::fmt::{Display, Write as _};
+use std::io::Write;
+use std::process::{ExitCode, Termination};
 use std::str::FromStr;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
-use time::OffsetDateTime;
+use crossterm::style::Print;
+use crossterm::{QueueableCommand, cursor};
+
+use time::format_description::well_known::Rfc3339;
 use time::macros::format_description;
+use time::{OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
 
 const MULTIPLIER_SECONDS: f64 = 1.0;
 const MULTIPLIER_MINUTES: f64 = 60.0;
 const MULTIPLIER_HOURS: f64 = 60.0 * 60.0;
 const MULTIPLIER_DAYS: f64 = 24.0 * 60.0 * 60.0;
 
+const MIN_FPS: f64 = 1.0;
+const MAX_FPS: f64 = 60.0;
+const ECO_MIN_INTERVAL: Duration = Duration::from_secs(1);
+pub const SRT_CUES_WARNING_THRESHOLD: u64 = 24 * 60 * 60;
+
+pub const MENU_PRESETS: [&str; 6] = ["5m", "10m", "15m", "25m", "30m", "1h"];
+
 #[derive(Debug, PartialEq)]
 enum SnoozeUnit {
     Seconds,
@@ -35,10 +48,512 @@ impl FromStr for SnoozeUnit {
     }
 }
 
-struct RemainingTime {
-    seconds: u64,
-    minutes: u64,
-    hours: u64,
+#[derive(Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum SnoozeResult {
+    Good = 0,
+    UserError = 1,
+    OsError = 2,
+    Interrupted = 3,
+}
+
+impl Termination for SnoozeResult {
+    fn report(self) -> ExitCode {
+        ExitCode::from(self as u8)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum SnoozeExit {
+    Result(SnoozeResult),
+    Signal(i32),
+}
+
+/// How snooze should exit. By default, snooze interrupted by a signal
+/// terminates with the same signal, like `sleep` does. Shells report that as
+/// status 128+n, but some of them also stop executing the whole command
+/// list. With `clean_exit`, snooze simply returns `Interrupted` status.
+#[allow(clippy::must_use_candidate)]
+pub fn snooze_exit(close_signal: Option<i32>, clean_exit: bool) -> SnoozeExit {
+    match close_signal {
+        None => SnoozeExit::Result(SnoozeResult::Good),
+        Some(_) if clean_exit => SnoozeExit::Result(SnoozeResult::Interrupted),
+        Some(signal) => SnoozeExit::Signal(signal),
+    }
+}
+
+/// Whether timer line should be finished with a newline after snooze
+/// exits with `signal` (0 when it finished normally). Shells usually print
+/// newline after a job killed by a signal, but `clean_exit` makes snooze
+/// exit normally, so it has to print one itself.
+#[allow(clippy::must_use_candidate)]
+pub fn ends_timer_line(signal: i32, clean_exit: bool) -> bool {
+    signal == 0 || clean_exit
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MenuKey {
+    Up,
+    Down,
+    Select,
+    Cancel,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum MenuAction {
+    Continue,
+    Select(usize),
+    Cancel,
+}
+
+pub struct Menu {
+    len: usize,
+    selected: usize,
+}
+
+impl Menu {
+    #[must_use]
+    pub fn new(len: usize) -> Self {
+        Self { len, selected: 0 }
+    }
+
+    #[must_use]
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    pub fn handle_key(&mut self, key: MenuKey) -> MenuAction {
+        match key {
+            MenuKey::Up => {
+                self.selected = self.selected.saturating_sub(1);
+                MenuAction::Continue
+            }
+            MenuKey::Down => {
+                self.selected = (self.selected + 1).min(self.len.saturating_sub(1));
+                MenuAction::Continue
+            }
+            MenuKey::Select if self.len > 0 => MenuAction::Select(self.selected),
+            MenuKey::Select | MenuKey::Cancel => MenuAction::Cancel,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum PrintTarget {
+    Terminal,
+    SnapshotFile,
+}
+
+/// Remaining time requested on demand (with SIGUSR1) goes to snapshot file,
+/// if one was configured. Regular updates always go to terminal.
+#[allow(clippy::must_use_candidate)]
+pub fn print_target(on_demand: bool, has_snapshot_file: bool) -> PrintTarget {
+    if on_demand && has_snapshot_file {
+        PrintTarget::SnapshotFile
+    } else {
+        PrintTarget::Terminal
+    }
+}
+
+/// Detects two presses happening within `window` from each other.
+pub struct DoubleTap {
+    window: Duration,
+    last_press: Option<Instant>,
+}
+
+impl DoubleTap {
+    #[must_use]
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            last_press: None,
+        }
+    }
+
+    /// Registers press at `now`. Returns `true` if previous press happened
+    /// within the window.
+    pub fn press(&mut self, now: Instant) -> bool {
+        let is_double = self
+            .last_press
+            .is_some_and(|last| now.saturating_duration_since(last) <= self.window);
+        self.last_press = Some(now);
+        is_double
+    }
+}
+
+/// Daily time window, like 09:00-17:00. Window ending before it starts, like
+/// 22:00-06:00, spans midnight.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BusinessHours {
+    start: Time,
+    end: Time,
+}
+
+impl FromStr for BusinessHours {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || format!("invalid business hours: {s}");
+        let (start, end) = s.split_once('-').ok_or_else(error)?;
+        let hour_minute = format_description!(version = 2, "[hour]:[minute]");
+        let start = Time::parse(start.trim(), hour_minute).map_err(|_| error())?;
+        let end = Time::parse(end.trim(), hour_minute).map_err(|_| error())?;
+        if start == end {
+            return Err(error());
+        }
+        Ok(Self { start, end })
+    }
+}
+
+impl BusinessHours {
+    /// Part of time between `from` and `to` that falls within business hours.
+    /// Days are split according to the UTC offset of `from`.
+    #[must_use]
+    pub fn elapsed(&self, from: OffsetDateTime, to: OffsetDateTime) -> Duration {
+        if to <= from {
+            return Duration::ZERO;
+        }
+        let to = to.to_offset(from.offset());
+        let mut elapsed = time::Duration::ZERO;
+        let mut day = from.date().previous_day().unwrap_or(from.date());
+        while day <= to.date() {
+            let window_start = day.with_time(self.start).assume_offset(from.offset());
+            let window_end = if self.end > self.start {
+                day.with_time(self.end)
+            } else {
+                day.next_day().unwrap_or(day).with_time(self.end)
+            }
+            .assume_offset(from.offset());
+            let overlap = to.min(window_end) - from.max(window_start);
+            if overlap.is_positive() {
+                elapsed += overlap;
+            }
+            let Some(next_day) = day.next_day() else {
+                break;
+            };
+            day = next_day;
+        }
+        Duration::try_from(elapsed).unwrap_or(Duration::ZERO)
+    }
+
+    /// Time left from `runtime` started at `start`, if only time within
+    /// business hours is counted.
+    #[must_use]
+    pub fn remaining(
+        &self,
+        start: OffsetDateTime,
+        now: OffsetDateTime,
+        runtime: Duration,
+    ) -> Duration {
+        runtime.saturating_sub(self.elapsed(start, now))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DisplayMode {
+    Clock,
+    RawSeconds,
+    SecondBar,
+}
+
+impl FromStr for DisplayMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "clock" => Ok(Self::Clock),
+            "raw-seconds" => Ok(Self::RawSeconds),
+            "second-bar" => Ok(Self::SecondBar),
+            _ => Err(format!("invalid display mode: {s}")),
+        }
+    }
+}
+
+/// Named set of defaults, read from config file. Options given explicitly
+/// on command line take precedence.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Profile {
+    name: String,
+    duration: Option<String>,
+    display: Option<DisplayMode>,
+    time_format: Option<String>,
+    danger: Option<Duration>,
+    ping_url: Option<String>,
+}
+
+impl Profile {
+    /// Profile duration is used only if time to pause wasn't given on command
+    /// line, neither as `explicit` arguments nor as absolute `target`.
+    #[must_use]
+    pub fn duration_args<'a>(&'a self, explicit: &[&'a str], target: bool) -> Vec<&'a str> {
+        match &self.duration {
+            Some(duration) if explicit.is_empty() && !target => {
+                duration.split_whitespace().collect()
+            }
+            _ => explicit.to_vec(),
+        }
+    }
+
+    #[must_use]
+    pub fn display_mode(&self, explicit: bool) -> Option<DisplayMode> {
+        if explicit { None } else { self.display }
+    }
+
+    /// Profile time format is used only if neither time format nor display
+    /// mode was given on command line.
+    #[must_use]
+    pub fn time_format<'a>(
+        &'a self,
+        explicit: Option<&'a str>,
+        explicit_display: bool,
+    ) -> Option<&'a str> {
+        match explicit {
+            Some(time_format) => Some(time_format),
+            None if explicit_display => None,
+            None => self.time_format.as_deref(),
+        }
+    }
+
+    #[must_use]
+    pub fn danger(&self, explicit: Option<Duration>) -> Option<Duration> {
+        explicit.or(self.danger)
+    }
+
+    #[must_use]
+    pub fn ping_url<'a>(&'a self, explicit: Option<&'a str>) -> Option<&'a str> {
+        explicit.or(self.ping_url.as_deref())
+    }
+}
+
+/// Parses profiles from INI-like config file:
+///
+/// ```text
+/// [focus]
+/// duration = 25m
+/// display = second-bar
+/// time-format = %M:%S
+/// danger = 10s
+/// ping-url = https://example.com/ping
+/// ```
+///
+/// # Errors
+///
+/// Returns description of the first line that couldn't be parsed.
+pub fn parse_profiles(contents: &str) -> Result<Vec<Profile>, String> {
+    let mut profiles: Vec<Profile> = Vec::new();
+    for (idx, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        let error = |msg: &str| format!("line {}: {msg}: {line}", idx + 1);
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            profiles.push(Profile {
+                name: name.trim().to_string(),
+                ..Profile::default()
+            });
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            return Err(error("expected key = value"));
+        };
+        let Some(profile) = profiles.last_mut() else {
+            return Err(error("option outside of profile"));
+        };
+        let value = value.trim().to_string();
+        match key.trim() {
+            "duration" => profile.duration = Some(value),
+            "display" => profile.display = Some(value.parse().map_err(|e: String| error(&e))?),
+            "time-format" => profile.time_format = Some(value),
+            "danger" => profile.danger = Some(parse_duration(&value).map_err(|e| error(&e))?),
+            "ping-url" => {
+                profile.ping_url = Some(parse_ping_url(&value).map_err(|e| error(&e))?);
+            }
+            _ => return Err(error("unknown option")),
+        }
+    }
+    Ok(profiles)
+}
+
+/// # Errors
+///
+/// Returns message listing available profiles if `name` is not one of them.
+pub fn find_profile(profiles: Vec<Profile>, name: &str) -> Result<Profile, String> {
+    let available: Vec<String> = profiles.iter().map(|p| p.name.clone()).collect();
+    profiles
+        .into_iter()
+        .find(|p| p.name == name)
+        .ok_or_else(|| {
+            format!(
+                "Profile {name} not found. Available profiles: {}",
+                available.join(", ")
+            )
+        })
+}
+
+/// Aggregated durations of completed snoozes.
+#[derive(Debug, PartialEq, Eq)]
+pub struct SnoozeStats {
+    count: u32,
+    total: Duration,
+    longest: Duration,
+    shortest: Duration,
+}
+
+impl SnoozeStats {
+    /// Line to append to stats file after snooze of `duration` finished at `end`.
+    #[must_use]
+    pub fn record(end: OffsetDateTime, duration: Duration) -> String {
+        format!("{} {:.3}\n", end.unix_timestamp(), duration.as_secs_f64())
+    }
+
+    fn parse_record(line: &str) -> Option<Duration> {
+        let mut parts = line.split_whitespace();
+        let _timestamp: i64 = parts.next()?.parse().ok()?;
+        let seconds: f64 = parts.next()?.parse().ok()?;
+        if parts.next().is_some() {
+            return None;
+        }
+        Duration::try_from_secs_f64(seconds).ok()
+    }
+
+    /// Aggregates stats file contents. Lines that can't be parsed are
+    /// ignored. Returns `None` if there are no valid records.
+    #[must_use]
+    pub fn from_records(contents: &str) -> Option<Self> {
+        contents.lines().filter_map(Self::parse_record).fold(
+            None,
+            |stats: Option<Self>, duration| {
+                Some(match stats {
+                    None => Self {
+                        count: 1,
+                        total: duration,
+                        longest: duration,
+                        shortest: duration,
+                    },
+                    Some(stats) => Self {
+                        count: stats.count + 1,
+                        total: stats.total.saturating_add(duration),
+                        longest: stats.longest.max(duration),
+                        shortest: stats.shortest.min(duration),
+                    },
+                })
+            },
+        )
+    }
+
+    #[must_use]
+    pub fn average(&self) -> Duration {
+        self.total / self.count.max(1)
+    }
+}
+
+/// Report printed for stats file `contents`. Missing file means that no
+/// snooze was recorded yet.
+///
+/// # Errors
+///
+/// Returns error if stats file couldn't be read for any other reason.
+pub fn stats_report(contents: std::io::Result<String>) -> std::io::Result<String> {
+    let contents = match contents {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(err) => return Err(err),
+    };
+    Ok(SnoozeStats::from_records(&contents).map_or_else(
+        || "No snoozes recorded yet".to_string(),
+        |stats| stats.to_string(),
+    ))
+}
+
+impl Display for SnoozeStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Count:    {}", self.count)?;
+        writeln!(f, "Total:    {}", format_spoken_pluralized(self.total))?;
+        writeln!(f, "Average:  {}", format_spoken_pluralized(self.average()))?;
+        writeln!(f, "Longest:  {}", format_spoken_pluralized(self.longest))?;
+        write!(f, "Shortest: {}", format_spoken_pluralized(self.shortest))
+    }
+}
+
+/// Remaining time split into components. `hours` are not limited to a day.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct RemainingTime {
+    pub seconds: u64,
+    pub minutes: u64,
+    pub hours: u64,
+}
+
+impl From<Duration> for RemainingTime {
+    fn from(input: Duration) -> Self {
+        let total_seconds = remaining_seconds(input);
+        let hours = total_seconds.div_euclid(60 * 60);
+        let remaining_minutes = total_seconds.rem_euclid(60 * 60);
+        Self {
+            seconds: remaining_minutes.rem_euclid(60),
+            minutes: remaining_minutes.div_euclid(60),
+            hours,
+        }
+    }
+}
+
+impl RemainingTime {
+    /// Formats remaining time according to `spec`. Supported tokens:
+    ///
+    /// - `%d` - days
+    /// - `%H` - hours within a day
+    /// - `%h` - total hours
+    /// - `%M` - minutes
+    /// - `%S` - seconds
+    /// - `%s` - total seconds
+    /// - `%%` - literal `%`
+    ///
+    /// All tokens except `%s` are padded with zero to two digits, unless
+    /// written with dash, like `%-M`. Unknown tokens are left as they are.
+    #[must_use]
+    pub fn format(&self, spec: &str) -> String {
+        let mut formatted = String::with_capacity(spec.len());
+        let mut rest = spec;
+        while let Some(start) = rest.find('%') {
+            formatted.push_str(&rest[..start]);
+            let token = &rest[start + 1..];
+            let (padded, token) = match token.strip_prefix('-') {
+                Some(token) => (false, token),
+                None => (true, token),
+            };
+            let mut chars = token.chars();
+            let value = match chars.next() {
+                Some('d') => Some(self.hours / 24),
+                Some('H') => Some(self.hours % 24),
+                Some('h') => Some(self.hours),
+                Some('M') => Some(self.minutes),
+                Some('S') => Some(self.seconds),
+                Some('s') => Some(self.total_seconds()),
+                _ => None,
+            };
+            match value {
+                Some(value) if padded && !token.starts_with('s') => {
+                    let _ = write!(formatted, "{value:02}");
+                }
+                Some(value) => formatted.push_str(&value.to_string()),
+                None if padded && token.starts_with('%') => formatted.push('%'),
+                None => {
+                    formatted.push('%');
+                    rest = &rest[start + 1..];
+                    continue;
+                }
+            }
+            rest = chars.as_str();
+        }
+        formatted.push_str(rest);
+        formatted
+    }
+
+    fn total_seconds(&self) -> u64 {
+        self.hours
+            .saturating_mul(60 * 60)
+            .saturating_add(self.minutes * 60 + self.seconds)
+    }
 }
 
 impl Display for RemainingTime {
@@ -99,6 +614,27 @@ fn parse_pause_arg(input: &str) -> Option<Duration> {
     Some(Duration::from_nanos(nano_seconds))
 }
 
+/// Returns number of seconds in argument without unit suffix, if it's over
+/// `threshold`. Such arguments might have been meant as minutes.
+#[allow(clippy::must_use_candidate)]
+pub fn unitless_seconds_over(input: &str, threshold: f64) -> Option<f64> {
+    let input = input.trim();
+    if input.ends_with(char::is_alphabetic) {
+        return None;
+    }
+    let (number, _) = split_unit(input)?;
+    (number > threshold).then_some(number)
+}
+
+/// Parses a single duration, for use as `argh` option parser.
+///
+/// # Errors
+///
+/// Returns error if `input` is not a valid time interval.
+pub fn parse_duration(input: &str) -> Result<Duration, String> {
+    parse_pause_arg(input).ok_or_else(|| format!("invalid time interval: {input}"))
+}
+
 #[allow(clippy::must_use_candidate)]
 pub fn sum_pause_args(args: &[&str]) -> Option<Duration> {
     args.iter()
@@ -113,6 +649,81 @@ pub fn sum_pause_args(args: &[&str]) -> Option<Duration> {
         })
 }
 
+/// Applies signed `adjustments`, like `+30s` or `-1m`, to `base` duration
+/// made of whitespace-separated fragments. Adjustments without sign are
+/// added.
+///
+/// # Errors
+///
+/// Returns error if anything can't be parsed, or result is negative or
+/// zero.
+pub fn adjust_base_duration(base: &str, adjustments: &[&str]) -> Result<Duration, String> {
+    let fragments: Vec<&str> = base.split_whitespace().collect();
+    let base_duration = sum_pause_args(&fragments)
+        .ok_or_else(|| format!("Invalid base time interval: {}", base.trim()))?;
+    let adjusted = adjustments
+        .iter()
+        .try_fold(base_duration, |acc, adjustment| {
+            let adjustment = adjustment.trim();
+            let (negative, value) = match adjustment.split_at_checked(1) {
+                Some(("-", value)) => (true, value),
+                Some(("+", value)) => (false, value),
+                _ => (false, adjustment),
+            };
+            let value = Some(value)
+                .filter(|value| !value.is_empty() && !value.starts_with(['+', '-']))
+                .and_then(parse_pause_arg)
+                .ok_or_else(|| format!("Invalid time adjustment supplied: {adjustment}"))?;
+            if negative {
+                acc.checked_sub(value)
+                    .ok_or_else(|| "Adjusted time interval is negative".to_string())
+            } else {
+                acc.checked_add(value)
+                    .ok_or_else(|| "Adjusted time interval is too long".to_string())
+            }
+        })?;
+    if adjusted.is_zero() {
+        return Err("Invalid time interval supplied".to_string());
+    }
+    Ok(adjusted)
+}
+
+/// Appends whitespace-separated duration fragments from `extra` to `args`.
+///
+/// # Errors
+///
+/// Returns the first fragment that is not a valid duration.
+pub fn merge_extra_pause_args<'a>(
+    args: &[&'a str],
+    extra: &'a str,
+) -> Result<Vec<&'a str>, &'a str> {
+    let mut merged = args.to_vec();
+    for fragment in extra.split_whitespace() {
+        if parse_pause_arg(fragment).is_none() {
+            return Err(fragment);
+        }
+        merged.push(fragment);
+    }
+    Ok(merged)
+}
+
+/// Parses comma-separated list of allowed durations.
+///
+/// # Errors
+///
+/// Returns the first item that is not a valid duration.
+pub fn parse_allowed_durations(input: &str) -> Result<Vec<Duration>, String> {
+    input
+        .split(',')
+        .map(|item| parse_duration(item).map_err(|_| item.trim().to_string()))
+        .collect()
+}
+
+#[allow(clippy::must_use_candidate)]
+pub fn is_allowed_duration(input: Duration, allowed: &[Duration]) -> bool {
+    allowed.contains(&input)
+}
+
 fn calc_wall_clock_end_time(
     beginning: OffsetDateTime,
     duration: Duration,
@@ -136,6 +747,20 @@ fn format_wall_clock_end_time(beginning: OffsetDateTime, end: OffsetDateTime) ->
     Some(format!("{date}{time}"))
 }
 
+fn format_wall_clock_midpoint_and_end_time(
+    beginning: OffsetDateTime,
+    duration: Duration,
+) -> Option<String> {
+    let half = Duration::from_secs(duration.as_secs() / 2);
+    let midpoint = calc_wall_clock_end_time(beginning, half)?;
+    let end = calc_wall_clock_end_time(beginning, duration)?;
+    Some(format!(
+        "half at {}, ends {}",
+        format_wall_clock_end_time(beginning, midpoint)?,
+        format_wall_clock_end_time(beginning, end)?
+    ))
+}
+
 #[allow(clippy::must_use_candidate)]
 pub fn wall_clock_end_time(input: Duration) -> Option<String> {
     let now = OffsetDateTime::now_local().unwrap_or_else(|_| OffsetDateTime::now_utc());
@@ -143,83 +768,1229 @@ pub fn wall_clock_end_time(input: Duration) -> Option<String> {
     format_wall_clock_end_time(now, end)
 }
 
+/// Formats absolute `target` the same way as end time of relative pause.
 #[allow(clippy::must_use_candidate)]
-pub fn format_remaining_time(input: Duration) -> String {
-    let mut total_seconds = input.as_secs();
-    if input.subsec_nanos() > 500_000_000 {
-        total_seconds = total_seconds.saturating_add(1);
-    }
-    let hours = total_seconds.div_euclid(60 * 60);
-    let remaining_minutes = total_seconds.rem_euclid(60 * 60);
-    let minutes = remaining_minutes.div_euclid(60);
-    let seconds = remaining_minutes.rem_euclid(60);
+pub fn wall_clock_target_time(target: OffsetDateTime) -> Option<String> {
+    let now = OffsetDateTime::now_local().unwrap_or_else(|_| OffsetDateTime::now_utc());
+    format_wall_clock_end_time(now, target.to_offset(now.offset()))
+}
 
-    let remaining = RemainingTime {
-        seconds,
-        minutes,
-        hours,
+#[allow(clippy::must_use_candidate)]
+pub fn wall_clock_midpoint_and_end_time(input: Duration) -> Option<String> {
+    let now = OffsetDateTime::now_local().unwrap_or_else(|_| OffsetDateTime::now_utc());
+    format_wall_clock_midpoint_and_end_time(now, input)
+}
+
+/// Describes `to` relative to `from`, e.g. "in 2 hours" or "3 minutes ago".
+/// Delta is expressed in the largest unit (seconds, minutes, hours or days)
+/// that fits at least once, rounded down. Deltas under a second are "now".
+#[allow(clippy::must_use_candidate)]
+pub fn humanize_delta(from: OffsetDateTime, to: OffsetDateTime) -> String {
+    let delta = to - from;
+    let total_seconds = delta.whole_seconds().unsigned_abs();
+    let (amount, unit) = match total_seconds {
+        0 => return "now".to_string(),
+        1..60 => (total_seconds, "second"),
+        60..3600 => (total_seconds / 60, "minute"),
+        3600..86400 => (total_seconds / 3600, "hour"),
+        _ => (total_seconds / 86400, "day"),
     };
-    remaining.to_string()
+    let plural = if amount == 1 { "" } else { "s" };
+    if delta.is_positive() {
+        format!("in {amount} {unit}{plural}")
+    } else {
+        format!("{amount} {unit}{plural} ago")
+    }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use rstest::rstest;
+/// Parses ISO 8601 date and time, like `2025-08-10T14:00:00`. Values without
+/// UTC offset are assumed to be in `offset`.
+#[allow(clippy::must_use_candidate)]
+pub fn parse_date_time(input: &str, offset: UtcOffset) -> Option<OffsetDateTime> {
+    let input = input.trim();
+    if let Ok(date_time) = OffsetDateTime::parse(input, &Rfc3339) {
+        return Some(date_time);
+    }
+    let date_time = PrimitiveDateTime::parse(
+        input,
+        format_description!(
+            version = 2,
+            "[year]-[month]-[day]T[hour]:[minute]:[second][optional [.[subsecond]]]"
+        ),
+    )
+    .ok()?;
+    Some(date_time.assume_offset(offset))
+}
 
-    #[rstest]
-    #[case("", Duration::ZERO)]
-    #[case(" ", Duration::ZERO)]
-    #[case("1", Duration::from_secs(1))]
-    #[case("1s", Duration::from_secs(1))]
-    #[case("0.5", Duration::from_millis(500))]
-    #[case("0.5s", Duration::from_millis(500))]
-    #[case("2m", Duration::from_secs(2 * 60))]
-    #[case("3h", Duration::from_secs(3 * 60 * 60))]
-    #[case("4d", Duration::from_secs(4 * 60 * 60 * 24))]
-    #[case("0.5m", Duration::from_secs(30))]
-    #[case("0.25m", Duration::from_secs(15))]
-    #[case("0.125m", Duration::from_millis(7500))]
-    #[case("0.5d", Duration::from_secs(12 * 60 * 60))]
-    #[case(" 1", Duration::from_secs(1))]
-    #[case(" 1\t\n", Duration::from_secs(1))]
-    fn test_parse_pause_arg_ok(#[case] input: &str, #[case] expected: Duration) {
-        let result = parse_pause_arg(input);
-        assert_eq!(result, Some(expected));
+/// Time left from `now` until `target`, if `target` is in the future.
+#[allow(clippy::must_use_candidate)]
+pub fn duration_until(now: OffsetDateTime, target: OffsetDateTime) -> Option<Duration> {
+    Duration::try_from(target - now)
+        .ok()
+        .filter(|duration| !duration.is_zero())
+}
+
+/// Parses minute of an hour written as `:MM`, like `:30`.
+#[allow(clippy::must_use_candidate)]
+pub fn parse_minute_of_hour(input: &str) -> Option<u8> {
+    let minute = input.trim().strip_prefix(':')?;
+    if minute.len() != 2 {
+        return None;
     }
+    minute.parse().ok().filter(|minute| *minute < 60)
+}
 
-    #[rstest]
-    #[case("0 5")]
-    #[case("s")]
-    #[case("1m2d")]
-    #[case("1m2")]
-    #[case("1y")]
-    #[case("1ms")]
-    fn test_parse_pause_arg_invalid(#[case] input: &str) {
-        let result = parse_pause_arg(input);
-        assert_eq!(result, None)
+/// Time left from `now` until the clock shows `minute` next time. If it's
+/// exactly that minute already, that is the next hour.
+#[allow(clippy::must_use_candidate)]
+pub fn duration_until_next_minute(now: OffsetDateTime, minute: u8) -> Duration {
+    let since_hour = u64::from(now.minute()) * 60 + u64::from(now.second());
+    let target = u64::from(minute) * 60;
+    let seconds = (target + 3600 - since_hour - 1) % 3600 + 1;
+    Duration::from_secs(seconds).saturating_sub(Duration::from_nanos(u64::from(now.nanosecond())))
+}
+
+#[allow(clippy::must_use_candidate)]
+pub fn remaining_until(end: OffsetDateTime, now: OffsetDateTime) -> Duration {
+    Duration::try_from(end - now).unwrap_or(Duration::ZERO)
+}
+
+/// What main loop should do after checking `remaining` time.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum LoopStep {
+    Finish,
+    Sleep(Duration),
+}
+
+/// Main loop finishes once nothing is remaining. Otherwise it sleeps until
+/// next refresh, but not past the end, so wall clock steps are noticed
+/// within one `refresh` interval.
+#[allow(clippy::must_use_candidate)]
+pub fn next_loop_step(remaining: Duration, refresh: Duration) -> LoopStep {
+    if remaining.is_zero() {
+        LoopStep::Finish
+    } else {
+        LoopStep::Sleep(remaining.min(refresh))
     }
+}
 
-    #[test]
-    fn test_sum_pause_args_empty() {
-        let input = [];
-        assert_eq!(None, sum_pause_args(&input));
+fn format_status(pid: i32, end: OffsetDateTime) -> String {
+    format!("{pid} {}\n", end.unix_timestamp_nanos())
+}
+
+/// Status file contents for process `pid` that is going to finish after
+/// `input`. Other processes may read it to query remaining time.
+#[allow(clippy::must_use_candidate)]
+pub fn status_file_contents(pid: i32, input: Duration) -> Option<String> {
+    let end = calc_wall_clock_end_time(OffsetDateTime::now_utc(), input)?;
+    Some(format_status(pid, end))
+}
+
+#[allow(clippy::must_use_candidate)]
+pub fn parse_status_file(contents: &str) -> Option<(i32, OffsetDateTime)> {
+    let mut parts = contents.split_whitespace();
+    let pid = parts.next()?.parse().ok()?;
+    let end_nanos = parts.next()?.parse().ok()?;
+    if parts.next().is_some() {
+        return None;
     }
+    let end = OffsetDateTime::from_unix_timestamp_nanos(end_nanos).ok()?;
+    Some((pid, end))
+}
 
-    #[test]
-    fn test_sum_pause_args_ok() {
-        let input = ["1s", "5s", "1m"];
-        let expected = Duration::from_secs(1 + 5 + 60);
-        assert_eq!(Some(expected), sum_pause_args(&input));
+/// Status file stores fixed end time, so it can't follow snooze that stops
+/// counting down outside of business hours or when paused. Returns
+/// conflicting option.
+#[allow(clippy::must_use_candidate)]
+pub fn status_file_conflict(business_hours: bool, sigint_pause: bool) -> Option<&'static str> {
+    if business_hours {
+        return Some("--business-hours");
     }
+    sigint_pause.then_some("--sigint-pause")
+}
 
-    #[test]
-    fn test_sum_pause_args_invalid() {
-        let input = ["1s", "5y", "1m"];
-        assert_eq!(None, sum_pause_args(&input));
+#[allow(clippy::must_use_candidate)]
+pub fn remaining_seconds(input: Duration) -> u64 {
+    let mut total_seconds = input.as_secs();
+    if input.subsec_nanos() > 500_000_000 {
+        total_seconds = total_seconds.saturating_add(1);
     }
+    total_seconds
+}
 
-    #[rstest]
+#[allow(clippy::must_use_candidate)]
+pub fn format_remaining_time(input: Duration) -> String {
+    RemainingTime::from(input).to_string()
+}
+
+fn format_srt_timestamp(input: Duration) -> String {
+    let total_seconds = input.as_secs();
+    format!(
+        "{:02}:{:02}:{:02},{:03}",
+        total_seconds / 3600,
+        total_seconds % 3600 / 60,
+        total_seconds % 60,
+        input.subsec_millis()
+    )
+}
+
+#[allow(clippy::must_use_candidate)]
+pub fn srt_cue_count(input: Duration) -> u64 {
+    input.as_secs() + u64::from(input.subsec_nanos() > 0)
+}
+
+/// Very long countdowns produce huge subtitles files, which deserves
+/// a warning.
+#[allow(clippy::must_use_candidate)]
+pub fn srt_cues_over_threshold(input: Duration) -> bool {
+    srt_cue_count(input) > SRT_CUES_WARNING_THRESHOLD
+}
+
+/// Writes SRT subtitles with one cue per second of countdown, each showing
+/// remaining time. Cues are written one at a time, so subtitles for long
+/// countdowns don't need to fit in memory.
+///
+/// # Errors
+///
+/// Returns error if writing to `writer` fails.
+pub fn write_srt_subtitles(writer: &mut impl Write, input: Duration) -> std::io::Result<()> {
+    for idx in 0..srt_cue_count(input) {
+        let start = Duration::from_secs(idx);
+        let end = Duration::from_secs(idx + 1).min(input);
+        write!(
+            writer,
+            "{}\n{} --> {}\n{}\n\n",
+            idx + 1,
+            format_srt_timestamp(start),
+            format_srt_timestamp(end),
+            format_compact_remaining_time(input.saturating_sub(start))
+        )?;
+    }
+    Ok(())
+}
+
+fn format_spoken(input: Duration, conjunction: bool) -> String {
+    let total_seconds = remaining_seconds(input);
+    let units = [
+        (total_seconds / 86400, "day"),
+        (total_seconds % 86400 / 3600, "hour"),
+        (total_seconds % 3600 / 60, "minute"),
+        (total_seconds % 60, "second"),
+    ];
+    let mut parts: Vec<String> = units
+        .iter()
+        .filter(|(amount, _)| *amount > 0)
+        .map(|(amount, unit)| {
+            let plural = if *amount == 1 { "" } else { "s" };
+            format!("{amount} {unit}{plural}")
+        })
+        .collect();
+    if parts.is_empty() {
+        return "0 seconds".to_string();
+    }
+    if conjunction && parts.len() > 1 {
+        let last = parts.pop().unwrap_or_default();
+        return format!("{} and {last}", parts.join(" "));
+    }
+    parts.join(" ")
+}
+
+/// Remaining time in words, like "1 hour 2 minutes". Units equal to zero
+/// are omitted.
+#[allow(clippy::must_use_candidate)]
+pub fn format_spoken_pluralized(input: Duration) -> String {
+    format_spoken(input, false)
+}
+
+/// Same as `format_spoken_pluralized`, but with "and" before the last unit,
+/// like "1 hour and 2 minutes".
+#[allow(clippy::must_use_candidate)]
+pub fn format_spoken_pluralized_with_and(input: Duration) -> String {
+    format_spoken(input, true)
+}
+
+/// Remaining time as total number of seconds, aligned to the same width as
+/// `format_remaining_time` output.
+#[allow(clippy::must_use_candidate)]
+pub fn format_raw_seconds(input: Duration) -> String {
+    format!("{:>9}", remaining_seconds(input))
+}
+
+#[allow(clippy::must_use_candidate)]
+pub fn format_compact_remaining_time(input: Duration) -> String {
+    format_remaining_time(input).trim_start().to_string()
+}
+
+/// Minimal JSON object with remaining time, for line-oriented consumers.
+#[allow(clippy::must_use_candidate)]
+pub fn json_line(remaining: Duration) -> String {
+    format!(
+        "{{\"remaining\": \"{}\"}}",
+        format_compact_remaining_time(remaining)
+    )
+}
+
+/// Bar of `width` segments representing part of a minute that is still left.
+/// Remaining time above a minute is displayed as a full bar.
+#[allow(clippy::must_use_candidate)]
+pub fn second_bar(remaining_secs: u64, width: usize) -> String {
+    let remaining_secs = usize::try_from(remaining_secs.min(60)).unwrap_or(60);
+    let filled = (remaining_secs * width).div_ceil(60);
+    format!("{}{}", "█".repeat(filled), " ".repeat(width - filled))
+}
+
+/// Queues printing `msg` at pinned position. Position is saved on the first
+/// update and restored on the following ones, so nothing is ever cleared.
+///
+/// # Errors
+///
+/// Returns error if writing to `writer` fails.
+pub fn queue_pinned_update(writer: &mut impl Write, msg: &str, first: bool) -> std::io::Result<()> {
+    writer.queue(cursor::Hide)?;
+    if first {
+        writer.queue(cursor::SavePosition)?;
+    } else {
+        writer.queue(cursor::RestorePosition)?;
+    }
+    writer.queue(Print(msg))?;
+    Ok(())
+}
+
+/// Number of full tenths of `total` that have already passed, from 0 to 10.
+#[allow(clippy::must_use_candidate)]
+pub fn progress_decile(remaining: Duration, total: Duration) -> u8 {
+    if total.is_zero() {
+        return 10;
+    }
+    let elapsed = total.saturating_sub(remaining);
+    let decile = (elapsed.as_nanos() * 10 / total.as_nanos()).min(10);
+    u8::try_from(decile).unwrap_or(10)
+}
+
+/// Returns `current` decile, if it's past the `last` one reported.
+#[allow(clippy::must_use_candidate)]
+pub fn crossed_decile(last: u8, current: u8) -> Option<u8> {
+    (current > last).then_some(current)
+}
+
+#[allow(clippy::must_use_candidate)]
+pub fn format_progress(decile: u8, remaining: Duration) -> String {
+    format!(
+        "{}% (remaining {})",
+        u16::from(decile) * 10,
+        format_compact_remaining_time(remaining)
+    )
+}
+
+/// Message to display at `tick`. Messages are rotated in order, starting
+/// over after the last one.
+#[allow(clippy::must_use_candidate)]
+pub fn pick_message(lines: &[String], tick: u64) -> Option<&str> {
+    let len = u64::try_from(lines.len()).ok().filter(|len| *len > 0)?;
+    let idx = usize::try_from(tick % len).ok()?;
+    lines.get(idx).map(String::as_str)
+}
+
+#[allow(clippy::must_use_candidate)]
+pub fn should_show_remaining(remaining: Duration, show_under: Option<Duration>) -> bool {
+    show_under.is_none_or(|threshold| remaining < threshold)
+}
+
+/// How remaining time should be colored in the final `danger` window.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum DangerPhase {
+    Safe,
+    Normal,
+    Highlighted,
+}
+
+/// Countdown blinks between highlighted and normal color every second, but
+/// only when `remaining` time is within `danger` window. Phase follows
+/// displayed seconds, so it doesn't depend on how often screen is updated.
+#[allow(clippy::must_use_candidate)]
+pub fn danger_phase(remaining: Duration, danger: Option<Duration>) -> DangerPhase {
+    match danger {
+        Some(danger) if remaining <= danger => {
+            if remaining_seconds(remaining).is_multiple_of(2) {
+                DangerPhase::Highlighted
+            } else {
+                DangerPhase::Normal
+            }
+        }
+        _ => DangerPhase::Safe,
+    }
+}
+
+/// Wraps `text` in OSC 8 escape sequence, so terminal displays it as
+/// a hyperlink to `url`.
+#[allow(clippy::must_use_candidate)]
+pub fn osc8_hyperlink(text: &str, url: &str) -> String {
+    format!("\x1b]8;;{url}\x1b\\{text}\x1b]8;;\x1b\\")
+}
+
+/// Characters that end a sentence or close a bracket rather than
+/// belong to the URL they follow.
+const URL_TRAILING_PUNCTUATION: [char; 10] = ['.', ',', ';', ':', '!', '?', ')', ']', '\'', '"'];
+
+/// Message displayed after snooze is completed. When `hyperlinks` are
+/// supported, http(s) URLs within message are made clickable.
+#[allow(clippy::must_use_candidate)]
+pub fn format_done_message(message: &str, hyperlinks: bool) -> String {
+    if !hyperlinks {
+        return message.to_string();
+    }
+    let mut formatted = String::with_capacity(message.len());
+    let mut rest = message;
+    while let Some(start) = rest.find("http") {
+        let (before, candidate) = rest.split_at(start);
+        formatted.push_str(before);
+        let end = candidate
+            .find(char::is_whitespace)
+            .unwrap_or(candidate.len());
+        let (word, after) = candidate.split_at(end);
+        if word.starts_with("https://") || word.starts_with("http://") {
+            let url = word.trim_end_matches(URL_TRAILING_PUNCTUATION);
+            formatted.push_str(&osc8_hyperlink(url, url));
+            formatted.push_str(&word[url.len()..]);
+            rest = after;
+        } else {
+            formatted.push_str("http");
+            rest = &candidate["http".len()..];
+        }
+    }
+    formatted.push_str(rest);
+    formatted
+}
+
+/// Word that must be typed to confirm start of snooze.
+pub const CONFIRM_WORD: &str = "START";
+
+/// What to do before starting snooze that requires confirmation.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ConfirmGate {
+    Proceed,
+    Prompt,
+    Refuse,
+}
+
+/// Snooze requiring confirmation starts only with `yes` flag, or after
+/// prompting on terminal. There is nobody to prompt without terminal.
+#[allow(clippy::must_use_candidate)]
+pub fn confirm_gate(require: bool, yes: bool, tty: bool) -> ConfirmGate {
+    match (require, yes, tty) {
+        (false, _, _) | (true, true, _) => ConfirmGate::Proceed,
+        (true, false, true) => ConfirmGate::Prompt,
+        (true, false, false) => ConfirmGate::Refuse,
+    }
+}
+
+#[allow(clippy::must_use_candidate)]
+pub fn is_confirmed(input: &str) -> bool {
+    input.trim_end_matches(['\r', '\n']) == CONFIRM_WORD
+}
+
+/// Parses health check URL. Only http(s) URLs are accepted, so value can't
+/// be mistaken for curl option.
+///
+/// # Errors
+///
+/// Returns error if `input` is not http(s) URL.
+pub fn parse_ping_url(input: &str) -> Result<String, String> {
+    let input = input.trim();
+    if input.starts_with("http://") || input.starts_with("https://") {
+        Ok(input.to_string())
+    } else {
+        Err(format!("invalid ping URL: {input}"))
+    }
+}
+
+/// Health check URL is pinged only when snooze was not interrupted by a signal.
+#[allow(clippy::must_use_candidate)]
+pub fn ping_url_on_exit(ping_url: Option<&str>, close_signal: Option<i32>) -> Option<&str> {
+    ping_url.filter(|_| close_signal.is_none())
+}
+
+/// Refresh interval for given number of frames per second. Frame rate is
+/// clamped between `MIN_FPS` and `MAX_FPS`.
+#[allow(clippy::must_use_candidate)]
+pub fn fps_to_interval(fps: f64) -> Option<Duration> {
+    if !fps.is_finite() || fps <= 0.0 {
+        return None;
+    }
+    Some(Duration::from_secs_f64(1.0 / fps.clamp(MIN_FPS, MAX_FPS)))
+}
+
+/// Parses frame rate into refresh interval, for use as `argh` option parser.
+///
+/// # Errors
+///
+/// Returns error if `input` is not a finite number greater than zero.
+pub fn parse_fps(input: &str) -> Result<Duration, String> {
+    input
+        .trim()
+        .parse()
+        .ok()
+        .and_then(fps_to_interval)
+        .ok_or_else(|| format!("invalid frame rate: {input}"))
+}
+
+/// In eco mode refresh interval is never shorter than `ECO_MIN_INTERVAL`,
+/// even if faster frame rate was requested.
+#[allow(clippy::must_use_candidate)]
+pub fn effective_refresh_interval(interval: Duration, eco: bool) -> Duration {
+    if eco {
+        interval.max(ECO_MIN_INTERVAL)
+    } else {
+        interval
+    }
+}
+
+/// Parses display scale factor, for use as `argh` option parser.
+///
+/// # Errors
+///
+/// Returns error if `input` is not a finite number greater than zero.
+pub fn parse_display_scale(input: &str) -> Result<f64, String> {
+    input
+        .trim()
+        .parse()
+        .ok()
+        .filter(|factor: &f64| factor.is_finite() && *factor > 0.0)
+        .ok_or_else(|| format!("invalid display scale: {input}"))
+}
+
+#[allow(clippy::must_use_candidate)]
+pub fn scale_remaining_time(input: Duration, factor: f64) -> Duration {
+    Duration::try_from_secs_f64(input.as_secs_f64() * factor).unwrap_or(Duration::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use time::macros::{datetime, time};
+
+    #[rstest]
+    #[case("", Duration::ZERO)]
+    #[case(" ", Duration::ZERO)]
+    #[case("1", Duration::from_secs(1))]
+    #[case("1s", Duration::from_secs(1))]
+    #[case("0.5", Duration::from_millis(500))]
+    #[case("0.5s", Duration::from_millis(500))]
+    #[case("2m", Duration::from_secs(2 * 60))]
+    #[case("3h", Duration::from_secs(3 * 60 * 60))]
+    #[case("4d", Duration::from_secs(4 * 60 * 60 * 24))]
+    #[case("0.5m", Duration::from_secs(30))]
+    #[case("0.25m", Duration::from_secs(15))]
+    #[case("0.125m", Duration::from_millis(7500))]
+    #[case("0.5d", Duration::from_secs(12 * 60 * 60))]
+    #[case(" 1", Duration::from_secs(1))]
+    #[case(" 1\t\n", Duration::from_secs(1))]
+    fn test_parse_pause_arg_ok(#[case] input: &str, #[case] expected: Duration) {
+        let result = parse_pause_arg(input);
+        assert_eq!(result, Some(expected));
+    }
+
+    #[rstest]
+    #[case("0 5")]
+    #[case("s")]
+    #[case("1m2d")]
+    #[case("1m2")]
+    #[case("1y")]
+    #[case("1ms")]
+    fn test_parse_pause_arg_invalid(#[case] input: &str) {
+        let result = parse_pause_arg(input);
+        assert_eq!(result, None)
+    }
+
+    #[rstest]
+    #[case(None, false, SnoozeExit::Result(SnoozeResult::Good))]
+    #[case(None, true, SnoozeExit::Result(SnoozeResult::Good))]
+    #[case(Some(2), false, SnoozeExit::Signal(2))]
+    #[case(Some(15), false, SnoozeExit::Signal(15))]
+    #[case(Some(2), true, SnoozeExit::Result(SnoozeResult::Interrupted))]
+    #[case(Some(15), true, SnoozeExit::Result(SnoozeResult::Interrupted))]
+    fn test_snooze_exit(
+        #[case] close_signal: Option<i32>,
+        #[case] clean_exit: bool,
+        #[case] expected: SnoozeExit,
+    ) {
+        assert_eq!(snooze_exit(close_signal, clean_exit), expected);
+    }
+
+    #[rstest]
+    #[case(0, false, true)]
+    #[case(0, true, true)]
+    #[case(2, false, false)]
+    #[case(2, true, true)]
+    #[case(15, true, true)]
+    fn test_ends_timer_line(#[case] signal: i32, #[case] clean_exit: bool, #[case] expected: bool) {
+        assert_eq!(ends_timer_line(signal, clean_exit), expected);
+    }
+
+    #[test]
+    fn test_snooze_result_exit_codes() {
+        assert_eq!(SnoozeResult::Good.report(), ExitCode::SUCCESS);
+        assert_eq!(SnoozeResult::Interrupted.report(), ExitCode::from(3));
+    }
+
+    #[rstest]
+    #[case(&[MenuKey::Select], MenuAction::Select(0))]
+    #[case(&[MenuKey::Down, MenuKey::Down, MenuKey::Select], MenuAction::Select(2))]
+    #[case(&[MenuKey::Down, MenuKey::Up, MenuKey::Select], MenuAction::Select(0))]
+    #[case(&[MenuKey::Up, MenuKey::Up, MenuKey::Select], MenuAction::Select(0))]
+    #[case(&[MenuKey::Down; 10], MenuAction::Continue)]
+    #[case(&[MenuKey::Down, MenuKey::Cancel], MenuAction::Cancel)]
+    fn test_menu_handle_key(#[case] keys: &[MenuKey], #[case] expected: MenuAction) {
+        let mut menu = Menu::new(MENU_PRESETS.len());
+        let mut action = MenuAction::Continue;
+        for key in keys {
+            action = menu.handle_key(*key);
+        }
+        assert_eq!(action, expected);
+    }
+
+    #[test]
+    fn test_menu_selection_stops_at_last_item() {
+        let mut menu = Menu::new(3);
+        for _ in 0..10 {
+            menu.handle_key(MenuKey::Down);
+        }
+        assert_eq!(menu.selected(), 2);
+        assert_eq!(menu.handle_key(MenuKey::Select), MenuAction::Select(2));
+    }
+
+    #[test]
+    fn test_menu_empty() {
+        let mut menu = Menu::new(0);
+        assert_eq!(menu.handle_key(MenuKey::Down), MenuAction::Continue);
+        assert_eq!(menu.handle_key(MenuKey::Select), MenuAction::Cancel);
+    }
+
+    #[rstest]
+    #[case(true, true, PrintTarget::SnapshotFile)]
+    #[case(true, false, PrintTarget::Terminal)]
+    #[case(false, true, PrintTarget::Terminal)]
+    #[case(false, false, PrintTarget::Terminal)]
+    fn test_print_target(
+        #[case] on_demand: bool,
+        #[case] has_snapshot_file: bool,
+        #[case] expected: PrintTarget,
+    ) {
+        assert_eq!(print_target(on_demand, has_snapshot_file), expected);
+    }
+
+    #[rstest]
+    #[case(&[0], &[false])]
+    #[case(&[0, 500], &[false, true])]
+    #[case(&[0, 1000], &[false, true])]
+    #[case(&[0, 1001], &[false, false])]
+    #[case(&[0, 3000, 3400], &[false, false, true])]
+    #[case(&[0, 1500, 3000], &[false, false, false])]
+    fn test_double_tap(#[case] presses_ms: &[u64], #[case] expected: &[bool]) {
+        let start = Instant::now();
+        let mut double_tap = DoubleTap::new(Duration::from_secs(1));
+        let result: Vec<bool> = presses_ms
+            .iter()
+            .map(|ms| double_tap.press(start + Duration::from_millis(*ms)))
+            .collect();
+        assert_eq!(result, expected);
+    }
+
+    #[rstest]
+    #[case("09:00-17:00", time!(09:00), time!(17:00))]
+    #[case(" 09:30 - 17:15 ", time!(09:30), time!(17:15))]
+    #[case("22:00-06:00", time!(22:00), time!(06:00))]
+    fn test_parse_business_hours_ok(#[case] input: &str, #[case] start: Time, #[case] end: Time) {
+        assert_eq!(input.parse(), Ok(BusinessHours { start, end }));
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("09:00")]
+    #[case("09:00-")]
+    #[case("09:00-25:00")]
+    #[case("9-17")]
+    #[case("09:00-09:00")]
+    fn test_parse_business_hours_invalid(#[case] input: &str) {
+        assert!(input.parse::<BusinessHours>().is_err());
+    }
+
+    #[rstest]
+    // started and finished within window
+    #[case(datetime!(2025-08-11 10:00 +2), datetime!(2025-08-11 12:30 +2), 150)]
+    // started before window
+    #[case(datetime!(2025-08-11 07:00 +2), datetime!(2025-08-11 10:00 +2), 60)]
+    // started and finished outside window
+    #[case(datetime!(2025-08-11 18:00 +2), datetime!(2025-08-11 23:00 +2), 0)]
+    // across day boundary
+    #[case(datetime!(2025-08-11 16:00 +2), datetime!(2025-08-12 10:00 +2), 120)]
+    // across multiple days
+    #[case(datetime!(2025-08-11 16:00 +2), datetime!(2025-08-13 10:00 +2), 600)]
+    // `to` in different offset
+    #[case(datetime!(2025-08-11 16:00 +2), datetime!(2025-08-12 08:00 UTC), 120)]
+    // `to` before `from`
+    #[case(datetime!(2025-08-11 16:00 +2), datetime!(2025-08-11 10:00 +2), 0)]
+    fn test_business_hours_elapsed(
+        #[case] from: OffsetDateTime,
+        #[case] to: OffsetDateTime,
+        #[case] expected_minutes: u64,
+    ) {
+        let hours: BusinessHours = "09:00-17:00".parse().unwrap();
+        let result = hours.elapsed(from, to);
+        assert_eq!(result, Duration::from_secs(expected_minutes * 60));
+    }
+
+    #[rstest]
+    #[case(datetime!(2025-08-11 23:00 +2), datetime!(2025-08-12 01:00 +2), 120)]
+    #[case(datetime!(2025-08-12 05:00 +2), datetime!(2025-08-12 23:00 +2), 120)]
+    #[case(datetime!(2025-08-11 12:00 +2), datetime!(2025-08-13 12:00 +2), 16 * 60)]
+    fn test_business_hours_elapsed_spanning_midnight(
+        #[case] from: OffsetDateTime,
+        #[case] to: OffsetDateTime,
+        #[case] expected_minutes: u64,
+    ) {
+        let hours: BusinessHours = "22:00-06:00".parse().unwrap();
+        let result = hours.elapsed(from, to);
+        assert_eq!(result, Duration::from_secs(expected_minutes * 60));
+    }
+
+    #[test]
+    fn test_business_hours_remaining() {
+        let hours: BusinessHours = "09:00-17:00".parse().unwrap();
+        let start = datetime!(2025-08-11 15:00 +2);
+        let runtime = Duration::from_secs(4 * 60 * 60);
+        let mut now = start;
+        let mut remaining = Vec::new();
+        while !hours.remaining(start, now, runtime).is_zero() {
+            remaining.push(hours.remaining(start, now, runtime).as_secs() / 60);
+            now += time::Duration::hours(2);
+        }
+        assert_eq!(
+            remaining,
+            [240, 120, 120, 120, 120, 120, 120, 120, 120, 120]
+        );
+        assert_eq!(now, datetime!(2025-08-12 11:00 +2));
+    }
+
+    #[rstest]
+    #[case("299", None)]
+    #[case("300", None)]
+    #[case("300.5", Some(300.5))]
+    #[case("301", Some(301.0))]
+    #[case(" 1000 ", Some(1000.0))]
+    #[case("1000s", None)]
+    #[case("1000m", None)]
+    #[case("1y", None)]
+    #[case("", None)]
+    fn test_unitless_seconds_over(#[case] input: &str, #[case] expected: Option<f64>) {
+        assert_eq!(unitless_seconds_over(input, 300.0), expected);
+    }
+
+    const PROFILES: &str = "
+# comment
+[focus]
+duration = 25m
+display = second-bar
+time-format = %M:%S
+danger = 10s
+ping-url = https://example.com/ping
+
+[ break ]
+duration=5m 30s
+display = raw-seconds
+
+[empty]
+";
+
+    #[test]
+    fn test_parse_profiles() {
+        let result = parse_profiles(PROFILES).unwrap();
+        let expected = vec![
+            Profile {
+                name: "focus".to_string(),
+                duration: Some("25m".to_string()),
+                display: Some(DisplayMode::SecondBar),
+                time_format: Some("%M:%S".to_string()),
+                danger: Some(Duration::from_secs(10)),
+                ping_url: Some("https://example.com/ping".to_string()),
+            },
+            Profile {
+                name: "break".to_string(),
+                duration: Some("5m 30s".to_string()),
+                display: Some(DisplayMode::RawSeconds),
+                ping_url: None,
+                ..Profile::default()
+            },
+            Profile {
+                name: "empty".to_string(),
+                ..Profile::default()
+            },
+        ];
+        assert_eq!(result, expected);
+    }
+
+    #[rstest]
+    #[case("duration = 5m", "line 1: option outside of profile: duration = 5m")]
+    #[case("[a]\nduration", "line 2: expected key = value: duration")]
+    #[case("[a]\ncolor = red", "line 2: unknown option: color = red")]
+    #[case(
+        "[a]\ndisplay = big",
+        "line 2: invalid display mode: big: display = big"
+    )]
+    #[case(
+        "[a]\ndanger = soon",
+        "line 2: invalid time interval: soon: danger = soon"
+    )]
+    #[case(
+        "[a]\nping-url = --output=/tmp/x",
+        "line 2: invalid ping URL: --output=/tmp/x: ping-url = --output=/tmp/x"
+    )]
+    fn test_parse_profiles_invalid(#[case] contents: &str, #[case] expected: &str) {
+        assert_eq!(parse_profiles(contents), Err(expected.to_string()));
+    }
+
+    #[test]
+    fn test_find_profile() {
+        let profiles = parse_profiles(PROFILES).unwrap();
+        let profile = find_profile(profiles, "break").unwrap();
+        assert_eq!(profile.duration_args(&[], false), ["5m", "30s"]);
+    }
+
+    #[test]
+    fn test_find_profile_missing() {
+        let profiles = parse_profiles(PROFILES).unwrap();
+        let result = find_profile(profiles, "nap");
+        let expected = "Profile nap not found. Available profiles: focus, break, empty";
+        assert_eq!(result, Err(expected.to_string()));
+    }
+
+    #[test]
+    fn test_profile_defaults() {
+        let profile = find_profile(parse_profiles(PROFILES).unwrap(), "focus").unwrap();
+        assert_eq!(profile.duration_args(&[], false), ["25m"]);
+        assert_eq!(profile.display_mode(false), Some(DisplayMode::SecondBar));
+        assert_eq!(profile.time_format(None, false), Some("%M:%S"));
+        assert_eq!(profile.danger(None), Some(Duration::from_secs(10)));
+        assert_eq!(profile.ping_url(None), Some("https://example.com/ping"));
+    }
+
+    #[test]
+    fn test_profile_explicit_flags_take_precedence() {
+        let profile = find_profile(parse_profiles(PROFILES).unwrap(), "focus").unwrap();
+        assert_eq!(profile.duration_args(&["10m", "5"], false), ["10m", "5"]);
+        assert_eq!(profile.display_mode(true), None);
+        assert_eq!(profile.time_format(Some("%s"), false), Some("%s"));
+        assert_eq!(profile.time_format(None, true), None);
+        assert_eq!(
+            profile.danger(Some(Duration::from_secs(3))),
+            Some(Duration::from_secs(3))
+        );
+        assert_eq!(
+            profile.ping_url(Some("https://example.org")),
+            Some("https://example.org")
+        );
+    }
+
+    #[test]
+    fn test_profile_target_takes_precedence() {
+        let profile = find_profile(parse_profiles(PROFILES).unwrap(), "focus").unwrap();
+        assert!(profile.duration_args(&[], true).is_empty());
+    }
+
+    #[test]
+    fn test_profile_without_settings() {
+        let profile = find_profile(parse_profiles(PROFILES).unwrap(), "empty").unwrap();
+        assert!(profile.duration_args(&[], false).is_empty());
+        assert_eq!(profile.duration_args(&["10m"], false), ["10m"]);
+        assert_eq!(profile.display_mode(false), None);
+        assert_eq!(profile.ping_url(None), None);
+    }
+
+    #[test]
+    fn test_snooze_stats_record() {
+        let end = datetime!(2025-08-10 14:00:00 UTC);
+        let result = SnoozeStats::record(end, Duration::from_millis(1500));
+        assert_eq!(result, "1754834400 1.500\n");
+    }
+
+    #[test]
+    fn test_snooze_stats_from_records() {
+        let contents = "\
+1754834400 300.000
+1754838000 1500.000
+1754841600 60.000
+1754845200 1800.000
+";
+        let result = SnoozeStats::from_records(contents).unwrap();
+        let expected = SnoozeStats {
+            count: 4,
+            total: Duration::from_secs(3660),
+            longest: Duration::from_secs(1800),
+            shortest: Duration::from_secs(60),
+        };
+        assert_eq!(result, expected);
+        assert_eq!(result.average(), Duration::from_secs(915));
+    }
+
+    #[test]
+    fn test_snooze_stats_ignores_corrupt_lines() {
+        let contents = "\
+1754834400 300.000
+garbage
+1754838000
+1754838000 abc
+1754838000 -5
+1754838000 NaN
+1754838000 60 1
+1754841600 60.000
+17548";
+        let result = SnoozeStats::from_records(contents).unwrap();
+        assert_eq!(result.count, 2);
+        assert_eq!(result.total, Duration::from_secs(360));
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("garbage\n")]
+    fn test_snooze_stats_empty(#[case] contents: &str) {
+        assert_eq!(SnoozeStats::from_records(contents), None);
+    }
+
+    #[rstest]
+    #[case(Ok(String::new()))]
+    #[case(Ok("garbage\n".to_string()))]
+    #[case(Err(std::io::ErrorKind::NotFound.into()))]
+    fn test_stats_report_empty(#[case] contents: std::io::Result<String>) {
+        assert_eq!(stats_report(contents).unwrap(), "No snoozes recorded yet");
+    }
+
+    #[test]
+    fn test_stats_report_records() {
+        let result = stats_report(Ok("1 60\n".to_string())).unwrap();
+        assert!(result.starts_with("Count:    1\n"));
+    }
+
+    #[test]
+    fn test_stats_report_read_error() {
+        let result = stats_report(Err(std::io::ErrorKind::PermissionDenied.into()));
+        assert_eq!(
+            result.unwrap_err().kind(),
+            std::io::ErrorKind::PermissionDenied
+        );
+    }
+
+    #[test]
+    fn test_snooze_stats_display() {
+        let stats = SnoozeStats::from_records("1 300\n2 1500\n3 60\n").unwrap();
+        let expected = "\
+Count:    3
+Total:    31 minutes
+Average:  10 minutes 20 seconds
+Longest:  25 minutes
+Shortest: 1 minute";
+        assert_eq!(stats.to_string(), expected);
+    }
+
+    #[rstest]
+    #[case(30_000, None, DangerPhase::Safe)]
+    #[case(30_000, Some(10), DangerPhase::Safe)]
+    #[case(10_001, Some(10), DangerPhase::Safe)]
+    #[case(10_000, Some(10), DangerPhase::Highlighted)]
+    #[case(9_600, Some(10), DangerPhase::Highlighted)]
+    #[case(9_400, Some(10), DangerPhase::Normal)]
+    #[case(9_000, Some(10), DangerPhase::Normal)]
+    #[case(8_000, Some(10), DangerPhase::Highlighted)]
+    #[case(7_000, Some(10), DangerPhase::Normal)]
+    #[case(1_000, Some(10), DangerPhase::Normal)]
+    #[case(0, Some(10), DangerPhase::Highlighted)]
+    #[case(0, Some(0), DangerPhase::Highlighted)]
+    fn test_danger_phase(
+        #[case] remaining_ms: u64,
+        #[case] danger_secs: Option<u64>,
+        #[case] expected: DangerPhase,
+    ) {
+        let remaining = Duration::from_millis(remaining_ms);
+        let danger = danger_secs.map(Duration::from_secs);
+        assert_eq!(danger_phase(remaining, danger), expected);
+    }
+
+    #[test]
+    fn test_danger_phase_independent_of_refresh_rate() {
+        // at 60 frames per second, color changes only once per second
+        let phases: Vec<DangerPhase> = (0..=180)
+            .rev()
+            .map(|frame| {
+                danger_phase(
+                    Duration::from_millis(frame * 1000 / 60),
+                    Some(Duration::from_secs(5)),
+                )
+            })
+            .collect();
+        let changes = phases.windows(2).filter(|pair| pair[0] != pair[1]).count();
+        assert_eq!(changes, 3);
+    }
+
+    #[rstest]
+    #[case("5m", &[], Duration::from_secs(300))]
+    #[case("5m\n", &["+30s"], Duration::from_secs(330))]
+    #[case("5m", &["-30s"], Duration::from_secs(270))]
+    #[case("5m", &["30"], Duration::from_secs(330))]
+    #[case("1h 5m", &["+1m", "-10m"], Duration::from_secs(3360))]
+    #[case("5m", &["-30s", "1m", "30s"], Duration::from_secs(360))]
+    #[case("  90  ", &["-1m"], Duration::from_secs(30))]
+    fn test_adjust_base_duration_ok(
+        #[case] base: &str,
+        #[case] adjustments: &[&str],
+        #[case] expected: Duration,
+    ) {
+        assert_eq!(adjust_base_duration(base, adjustments), Ok(expected));
+    }
+
+    #[rstest]
+    #[case("", &["+30s"], "Invalid base time interval: ")]
+    #[case("soon\n", &["+30s"], "Invalid base time interval: soon")]
+    #[case("5m", &["+abc"], "Invalid time adjustment supplied: +abc")]
+    #[case("5m", &["-"], "Invalid time adjustment supplied: -")]
+    #[case("5m", &["--1m"], "Invalid time adjustment supplied: --1m")]
+    #[case("5m", &["-5m"], "Invalid time interval supplied")]
+    #[case("5m", &["-6m"], "Adjusted time interval is negative")]
+    #[case("5m", &["+1m", "-7m"], "Adjusted time interval is negative")]
+    fn test_adjust_base_duration_err(
+        #[case] base: &str,
+        #[case] adjustments: &[&str],
+        #[case] expected: &str,
+    ) {
+        assert_eq!(
+            adjust_base_duration(base, adjustments),
+            Err(expected.to_string())
+        );
+    }
+
+    #[test]
+    fn test_osc8_hyperlink() {
+        let result = osc8_hyperlink("ticket", "https://example.com/T-1");
+        assert_eq!(
+            result,
+            "\x1b]8;;https://example.com/T-1\x1b\\ticket\x1b]8;;\x1b\\"
+        );
+    }
+
+    #[rstest]
+    #[case("Break is over", true, "Break is over")]
+    #[case(
+        "See https://example.com/T-1 now",
+        true,
+        "See \x1b]8;;https://example.com/T-1\x1b\\https://example.com/T-1\x1b]8;;\x1b\\ now"
+    )]
+    #[case(
+        "http://a.example\thttps://b.example",
+        true,
+        "\x1b]8;;http://a.example\x1b\\http://a.example\x1b]8;;\x1b\\\t\x1b]8;;https://b.example\x1b\\https://b.example\x1b]8;;\x1b\\"
+    )]
+    #[case("httpie and http:/broken", true, "httpie and http:/broken")]
+    #[case(
+        "see https://example.com/T-1.",
+        true,
+        "see \x1b]8;;https://example.com/T-1\x1b\\https://example.com/T-1\x1b]8;;\x1b\\."
+    )]
+    #[case(
+        "(https://example.com/T-1)",
+        true,
+        "(\x1b]8;;https://example.com/T-1\x1b\\https://example.com/T-1\x1b]8;;\x1b\\)"
+    )]
+    #[case(
+        "Read \"https://example.com/T-1\"!",
+        true,
+        "Read \"\x1b]8;;https://example.com/T-1\x1b\\https://example.com/T-1\x1b]8;;\x1b\\\"!"
+    )]
+    #[case(
+        "See https://example.com/T-1 now",
+        false,
+        "See https://example.com/T-1 now"
+    )]
+    fn test_format_done_message(
+        #[case] message: &str,
+        #[case] hyperlinks: bool,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(format_done_message(message, hyperlinks), expected);
+    }
+
+    #[rstest]
+    #[case(Duration::from_millis(40), false, Duration::from_millis(40))]
+    #[case(Duration::from_millis(40), true, Duration::from_secs(1))]
+    #[case(Duration::from_millis(999), true, Duration::from_secs(1))]
+    #[case(Duration::from_secs(1), true, Duration::from_secs(1))]
+    #[case(Duration::from_secs(2), true, Duration::from_secs(2))]
+    #[case(Duration::from_secs(2), false, Duration::from_secs(2))]
+    fn test_effective_refresh_interval(
+        #[case] interval: Duration,
+        #[case] eco: bool,
+        #[case] expected: Duration,
+    ) {
+        assert_eq!(effective_refresh_interval(interval, eco), expected);
+    }
+
+    #[rstest]
+    #[case(Duration::from_secs(90), r#"{"remaining": "1:30"}"#)]
+    #[case(Duration::from_secs(5), r#"{"remaining": "5"}"#)]
+    #[case(Duration::from_millis(59_500), r#"{"remaining": "59"}"#)]
+    #[case(Duration::from_secs(3723), r#"{"remaining": "1:02:03"}"#)]
+    fn test_json_line(#[case] remaining: Duration, #[case] expected: &str) {
+        assert_eq!(json_line(remaining), expected);
+    }
+
+    #[rstest]
+    #[case(false, false, false, ConfirmGate::Proceed)]
+    #[case(false, false, true, ConfirmGate::Proceed)]
+    #[case(false, true, false, ConfirmGate::Proceed)]
+    #[case(false, true, true, ConfirmGate::Proceed)]
+    #[case(true, true, false, ConfirmGate::Proceed)]
+    #[case(true, true, true, ConfirmGate::Proceed)]
+    #[case(true, false, true, ConfirmGate::Prompt)]
+    #[case(true, false, false, ConfirmGate::Refuse)]
+    fn test_confirm_gate(
+        #[case] require: bool,
+        #[case] yes: bool,
+        #[case] tty: bool,
+        #[case] expected: ConfirmGate,
+    ) {
+        assert_eq!(confirm_gate(require, yes, tty), expected);
+    }
+
+    #[rstest]
+    #[case("START\n", true)]
+    #[case("START\r\n", true)]
+    #[case("START", true)]
+    #[case("start\n", false)]
+    #[case(" START\n", false)]
+    #[case("STARTED\n", false)]
+    #[case("\n", false)]
+    #[case("", false)]
+    fn test_is_confirmed(#[case] input: &str, #[case] expected: bool) {
+        assert_eq!(is_confirmed(input), expected);
+    }
+
+    #[test]
+    fn test_remaining_time_from_duration() {
+        let result = RemainingTime::from(Duration::from_millis(93_784_600));
+        let expected = RemainingTime {
+            seconds: 5,
+            minutes: 3,
+            hours: 26,
+        };
+        assert_eq!(result, expected);
+    }
+
+    #[rstest]
+    #[case(Duration::from_secs(3723), "%H:%M:%S", "01:02:03")]
+    #[case(Duration::from_secs(3723), "%-H:%-M:%-S", "1:2:3")]
+    #[case(Duration::from_secs(5), "%M:%S", "00:05")]
+    #[case(Duration::from_secs(93_784), "%dd %H:%M:%S", "01d 02:03:04")]
+    #[case(Duration::from_secs(93_784), "%-d days, %-Hh", "1 days, 2h")]
+    #[case(Duration::from_secs(93_784), "%h:%M", "26:03")]
+    #[case(Duration::from_secs(360_000), "%h hours", "100 hours")]
+    #[case(Duration::from_secs(3723), "%s seconds", "3723 seconds")]
+    #[case(Duration::from_secs(3723), "%-s", "3723")]
+    #[case(Duration::from_secs(30), "100%% %S", "100% 30")]
+    #[case(Duration::from_secs(30), "%x %-x %-% %S %", "%x %-x %-% 30 %")]
+    #[case(Duration::from_secs(30), "", "")]
+    #[case(Duration::from_secs(30), "plain text", "plain text")]
+    fn test_remaining_time_format(
+        #[case] input: Duration,
+        #[case] spec: &str,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(RemainingTime::from(input).format(spec), expected);
+    }
+
+    #[test]
+    fn test_parse_duration_ok() {
+        assert_eq!(parse_duration("5m"), Ok(Duration::from_secs(5 * 60)));
+    }
+
+    #[test]
+    fn test_parse_duration_invalid() {
+        assert_eq!(
+            parse_duration("5y"),
+            Err("invalid time interval: 5y".to_string())
+        );
+    }
+
+    #[test]
+    fn test_sum_pause_args_empty() {
+        let input = [];
+        assert_eq!(None, sum_pause_args(&input));
+    }
+
+    #[test]
+    fn test_sum_pause_args_ok() {
+        let input = ["1s", "5s", "1m"];
+        let expected = Duration::from_secs(1 + 5 + 60);
+        assert_eq!(Some(expected), sum_pause_args(&input));
+    }
+
+    #[test]
+    fn test_sum_pause_args_invalid() {
+        let input = ["1s", "5y", "1m"];
+        assert_eq!(None, sum_pause_args(&input));
+    }
+
+    #[rstest]
+    #[case(&["1m"], "", &["1m"])]
+    #[case(&["1m"], "30s", &["1m", "30s"])]
+    #[case(&["1m", "2"], " 5m\t1h\n", &["1m", "2", "5m", "1h"])]
+    #[case(&[], "5m", &["5m"])]
+    fn test_merge_extra_pause_args_ok(
+        #[case] args: &[&str],
+        #[case] extra: &str,
+        #[case] expected: &[&str],
+    ) {
+        let result = merge_extra_pause_args(args, extra);
+        assert_eq!(result, Ok(expected.to_vec()));
+    }
+
+    #[test]
+    fn test_merge_extra_pause_args_invalid() {
+        let result = merge_extra_pause_args(&["1m"], "5m 1y 2s");
+        assert_eq!(result, Err("1y"));
+    }
+
+    #[test]
+    fn test_merge_extra_pause_args_sum() {
+        let merged = merge_extra_pause_args(&["1m"], "30s").unwrap();
+        assert_eq!(sum_pause_args(&merged), Some(Duration::from_secs(90)));
+    }
+
+    #[test]
+    fn test_parse_allowed_durations_ok() {
+        let result = parse_allowed_durations("5m, 15m,1500");
+        let expected = vec![
+            Duration::from_secs(5 * 60),
+            Duration::from_secs(15 * 60),
+            Duration::from_secs(25 * 60),
+        ];
+        assert_eq!(result, Ok(expected));
+    }
+
+    #[test]
+    fn test_parse_allowed_durations_invalid() {
+        let result = parse_allowed_durations("5m, 1y ,25m");
+        assert_eq!(result, Err("1y".to_string()));
+    }
+
+    #[rstest]
+    #[case(Duration::from_secs(5 * 60), true)]
+    #[case(Duration::from_secs(25 * 60), true)]
+    #[case(Duration::from_secs(10 * 60), false)]
+    #[case(Duration::from_millis(5 * 60 * 1000 + 1), false)]
+    fn test_is_allowed_duration(#[case] input: Duration, #[case] expected: bool) {
+        let allowed = parse_allowed_durations("5m,15m,25m").unwrap();
+        assert_eq!(is_allowed_duration(input, &allowed), expected);
+    }
+
+    #[rstest]
     #[case(1565442000, 3600)]
     #[case(1709208000, 3600)] // leap year
     #[case(1740744000, 3600)] // February non-leap year
@@ -253,6 +2024,27 @@ mod tests {
         assert_eq!(result, Some(expected.to_string()));
     }
 
+    #[rstest]
+    #[case(1565442000, 5400.0, "half at 13:45:00, ends 14:30:00")]
+    #[case(1565442000, 3601.0, "half at 13:30:00, ends 14:00:01")] // odd duration
+    #[case(1565442000, 3.5, "half at 13:00:01, ends 13:00:03")] // sub-second remainder
+    #[case(
+        1745539140,
+        3600.0,
+        "half at 2025-04-25 00:29:00, ends 2025-04-25 00:59:00"
+    )]
+    #[case(1745535540, 7200.0, "half at 23:59:00, ends 2025-04-25 00:59:00")]
+    fn test_format_wall_clock_midpoint_and_end_time(
+        #[case] beginning_ts: i64,
+        #[case] duration: f64,
+        #[case] expected: &str,
+    ) {
+        let beginning = OffsetDateTime::from_unix_timestamp(beginning_ts).unwrap();
+        let result =
+            format_wall_clock_midpoint_and_end_time(beginning, Duration::from_secs_f64(duration));
+        assert_eq!(result, Some(expected.to_string()));
+    }
+
     #[rstest]
     #[case(Duration::from_secs(1), "        1")]
     #[case(Duration::from_secs(11), "       11")]
@@ -269,4 +2061,516 @@ mod tests {
         let result = format_remaining_time(input);
         assert_eq!(result, expected);
     }
+
+    #[rstest]
+    #[case(Duration::from_secs(1), "1")]
+    #[case(Duration::from_secs(61), "1:01")]
+    #[case(Duration::from_secs(701), "11:41")]
+    #[case(Duration::from_secs(7100), "1:58:20")]
+    #[case(Duration::from_secs(604800), "168:00:00")]
+    #[case(Duration::ZERO, "0")]
+    fn test_format_compact_remaining_time(#[case] input: Duration, #[case] expected: &str) {
+        let result = format_compact_remaining_time(input);
+        assert_eq!(result, expected);
+    }
+
+    #[rstest]
+    #[case(0, "now")]
+    #[case(1, "in 1 second")]
+    #[case(-1, "1 second ago")]
+    #[case(45, "in 45 seconds")]
+    #[case(-45, "45 seconds ago")]
+    #[case(60, "in 1 minute")]
+    #[case(179, "in 2 minutes")]
+    #[case(-180, "3 minutes ago")]
+    #[case(3599, "in 59 minutes")]
+    #[case(7200, "in 2 hours")]
+    #[case(-3600, "1 hour ago")]
+    #[case(86399, "in 23 hours")]
+    #[case(86400, "in 1 day")]
+    #[case(-3 * 86400 - 5, "3 days ago")]
+    fn test_humanize_delta(#[case] delta: i64, #[case] expected: &str) {
+        let from = OffsetDateTime::from_unix_timestamp(1754690400).unwrap();
+        let to = OffsetDateTime::from_unix_timestamp(1754690400 + delta).unwrap();
+        assert_eq!(humanize_delta(from, to), expected);
+    }
+
+    #[test]
+    fn test_humanize_delta_subsecond() {
+        let from = OffsetDateTime::from_unix_timestamp(1754690400).unwrap();
+        let to = from + time::Duration::milliseconds(400);
+        assert_eq!(humanize_delta(from, to), "now");
+        assert_eq!(humanize_delta(to, from), "now");
+    }
+
+    #[rstest]
+    #[case("2025-08-10T14:00:00", 0, 1754834400)]
+    #[case("2025-08-10T14:00:00", 2, 1754827200)]
+    #[case("2025-08-10T14:00:00.5", 0, 1754834400)]
+    #[case("2025-08-10T14:00:00Z", 2, 1754834400)]
+    #[case("2025-08-10T14:00:00+02:00", 0, 1754827200)]
+    #[case("2025-08-10T14:00:00-05:30", 2, 1754854200)]
+    #[case(" 2025-08-10T14:00:00 ", 0, 1754834400)]
+    fn test_parse_date_time_ok(
+        #[case] input: &str,
+        #[case] offset_hours: i8,
+        #[case] expected_ts: i64,
+    ) {
+        let offset = UtcOffset::from_hms(offset_hours, 0, 0).unwrap();
+        let result = parse_date_time(input, offset).map(OffsetDateTime::unix_timestamp);
+        assert_eq!(result, Some(expected_ts));
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("14:00")]
+    #[case("2025-08-10")]
+    #[case("2025-08-10T14:00")]
+    #[case("2025-08-10 14:00:00")]
+    #[case("2025-02-30T14:00:00")]
+    #[case("2025-08-10T25:00:00")]
+    fn test_parse_date_time_invalid(#[case] input: &str) {
+        assert_eq!(parse_date_time(input, UtcOffset::UTC), None);
+    }
+
+    #[rstest]
+    #[case("2025-08-10T14:00:00", Some(Duration::from_secs(4 * 60 * 60)))]
+    #[case("2025-08-11T10:00:30", Some(Duration::from_secs(24 * 60 * 60 + 30)))]
+    #[case("2025-08-10T12:00:00+02:00", None)]
+    #[case("2025-08-10T10:00:00", None)]
+    #[case("2025-08-10T09:00:00", None)]
+    fn test_duration_until_parsed(#[case] input: &str, #[case] expected: Option<Duration>) {
+        let now = datetime!(2025-08-10 10:00:00 UTC);
+        let target = parse_date_time(input, now.offset()).unwrap();
+        assert_eq!(duration_until(now, target), expected);
+    }
+
+    #[rstest]
+    #[case(":00", Some(0))]
+    #[case(":30", Some(30))]
+    #[case(" :59 ", Some(59))]
+    #[case(":60", None)]
+    #[case(":5", None)]
+    #[case("30", None)]
+    #[case(":ab", None)]
+    #[case(":-1", None)]
+    fn test_parse_minute_of_hour(#[case] input: &str, #[case] expected: Option<u8>) {
+        assert_eq!(parse_minute_of_hour(input), expected);
+    }
+
+    #[rstest]
+    #[case(datetime!(2025-08-10 14:12:00 UTC), 0, 48 * 60)]
+    #[case(datetime!(2025-08-10 14:12:00 UTC), 30, 18 * 60)]
+    #[case(datetime!(2025-08-10 14:42:00 UTC), 30, 48 * 60)]
+    #[case(datetime!(2025-08-10 14:12:30 UTC), 0, 47 * 60 + 30)]
+    #[case(datetime!(2025-08-10 14:00:00 UTC), 0, 60 * 60)]
+    #[case(datetime!(2025-08-10 14:30:00 UTC), 30, 60 * 60)]
+    #[case(datetime!(2025-08-10 14:00:30 UTC), 0, 59 * 60 + 30)]
+    #[case(datetime!(2025-08-10 14:29:59 UTC), 30, 1)]
+    #[case(datetime!(2025-08-10 23:45:00 +2), 0, 15 * 60)]
+    fn test_duration_until_next_minute(
+        #[case] now: OffsetDateTime,
+        #[case] minute: u8,
+        #[case] expected_secs: u64,
+    ) {
+        let result = duration_until_next_minute(now, minute);
+        assert_eq!(result, Duration::from_secs(expected_secs));
+    }
+
+    #[test]
+    fn test_duration_until_next_minute_subsecond() {
+        let now = datetime!(2025-08-10 14:29:59.25 UTC);
+        let result = duration_until_next_minute(now, 30);
+        assert_eq!(result, Duration::from_millis(750));
+    }
+
+    #[rstest]
+    #[case(1754690400, 1754690490, Duration::from_secs(90))]
+    #[case(1754690400, 1754690400, Duration::ZERO)]
+    #[case(1754690490, 1754690400, Duration::ZERO)]
+    fn test_remaining_until(#[case] now_ts: i64, #[case] end_ts: i64, #[case] expected: Duration) {
+        let now = OffsetDateTime::from_unix_timestamp(now_ts).unwrap();
+        let end = OffsetDateTime::from_unix_timestamp(end_ts).unwrap();
+        assert_eq!(remaining_until(end, now), expected);
+    }
+
+    /// Runs main loop decisions against simulated wall clock, which advances
+    /// by time slept, and jumps by `jump` after `jump_after` sleeps.
+    /// Returns number of sleeps and time on the clock when loop finished.
+    fn simulate_wall_clock_loop(
+        target: OffsetDateTime,
+        mut now: OffsetDateTime,
+        jump_after: usize,
+        jump: time::Duration,
+    ) -> (usize, OffsetDateTime) {
+        let refresh = Duration::from_secs(1);
+        let mut sleeps = 0;
+        while let LoopStep::Sleep(sleep) = next_loop_step(remaining_until(target, now), refresh) {
+            assert!(sleep <= refresh);
+            sleeps += 1;
+            now += sleep;
+            if sleeps == jump_after {
+                now += jump;
+            }
+        }
+        (sleeps, now)
+    }
+
+    #[rstest]
+    #[case::no_jump(0, 10, datetime!(2025-08-10 14:00:00 UTC))]
+    #[case::forward(5, 5, datetime!(2025-08-10 14:00:00 UTC))]
+    #[case::backward(-5, 15, datetime!(2025-08-10 14:00:00 UTC))]
+    #[case::backward_hour(-3600, 3610, datetime!(2025-08-10 14:00:00 UTC))]
+    #[case::forward_past_target(3600, 3, datetime!(2025-08-10 14:59:53 UTC))]
+    fn test_wall_clock_loop_follows_clock_step(
+        #[case] jump_secs: i64,
+        #[case] expected_sleeps: usize,
+        #[case] expected_end: OffsetDateTime,
+    ) {
+        let target = datetime!(2025-08-10 14:00:00 UTC);
+        let start = datetime!(2025-08-10 13:59:50 UTC);
+        let jump = time::Duration::seconds(jump_secs);
+        let result = simulate_wall_clock_loop(target, start, 3, jump);
+        assert_eq!(result, (expected_sleeps, expected_end));
+    }
+
+    #[rstest]
+    #[case(Duration::ZERO, LoopStep::Finish)]
+    #[case(
+        Duration::from_millis(500),
+        LoopStep::Sleep(Duration::from_millis(500))
+    )]
+    #[case(Duration::from_secs(1), LoopStep::Sleep(Duration::from_secs(1)))]
+    #[case(Duration::from_secs(90), LoopStep::Sleep(Duration::from_secs(1)))]
+    fn test_next_loop_step(#[case] remaining: Duration, #[case] expected: LoopStep) {
+        assert_eq!(next_loop_step(remaining, Duration::from_secs(1)), expected);
+    }
+
+    #[test]
+    fn test_status_file_roundtrip() {
+        let end = OffsetDateTime::from_unix_timestamp_nanos(1_754_690_400_250_000_000).unwrap();
+        let contents = format_status(1234, end);
+        assert_eq!(contents, "1234 1754690400250000000\n");
+        assert_eq!(parse_status_file(&contents), Some((1234, end)));
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("1234")]
+    #[case("1234 abc")]
+    #[case("abc 1754690400250000000")]
+    #[case("1234 1754690400250000000 1")]
+    fn test_parse_status_file_invalid(#[case] contents: &str) {
+        assert_eq!(parse_status_file(contents), None);
+    }
+
+    #[rstest]
+    #[case(false, false, None)]
+    #[case(true, false, Some("--business-hours"))]
+    #[case(false, true, Some("--sigint-pause"))]
+    #[case(true, true, Some("--business-hours"))]
+    fn test_status_file_conflict(
+        #[case] business_hours: bool,
+        #[case] sigint_pause: bool,
+        #[case] expected: Option<&str>,
+    ) {
+        assert_eq!(status_file_conflict(business_hours, sigint_pause), expected);
+    }
+
+    #[rstest]
+    #[case(Duration::ZERO, "        0")]
+    #[case(Duration::from_secs(1), "        1")]
+    #[case(Duration::from_secs(61), "       61")]
+    #[case(Duration::from_secs(5399), "     5399")]
+    #[case(Duration::from_millis(5_398_600), "     5399")]
+    #[case(Duration::from_millis(5_398_400), "     5398")]
+    #[case(Duration::from_secs(604800), "   604800")]
+    #[case(Duration::from_secs(1_000_000_000), "1000000000")]
+    fn test_format_raw_seconds(#[case] input: Duration, #[case] expected: &str) {
+        assert_eq!(format_raw_seconds(input), expected);
+    }
+
+    #[rstest]
+    #[case(Duration::ZERO, "0 seconds")]
+    #[case(Duration::from_secs(1), "1 second")]
+    #[case(Duration::from_secs(2), "2 seconds")]
+    #[case(Duration::from_secs(60), "1 minute")]
+    #[case(Duration::from_secs(120), "2 minutes")]
+    #[case(Duration::from_secs(3660), "1 hour 1 minute")]
+    #[case(Duration::from_secs(7322), "2 hours 2 minutes 2 seconds")]
+    #[case(Duration::from_secs(3601), "1 hour 1 second")]
+    #[case(Duration::from_secs(90061), "1 day 1 hour 1 minute 1 second")]
+    #[case(Duration::from_millis(59_900), "1 minute")]
+    fn test_format_spoken_pluralized(#[case] input: Duration, #[case] expected: &str) {
+        assert_eq!(format_spoken_pluralized(input), expected);
+    }
+
+    #[rstest]
+    #[case(Duration::ZERO, "0 seconds")]
+    #[case(Duration::from_secs(60), "1 minute")]
+    #[case(Duration::from_secs(3660), "1 hour and 1 minute")]
+    #[case(Duration::from_secs(7322), "2 hours 2 minutes and 2 seconds")]
+    fn test_format_spoken_pluralized_with_and(#[case] input: Duration, #[case] expected: &str) {
+        assert_eq!(format_spoken_pluralized_with_and(input), expected);
+    }
+
+    #[rstest]
+    #[case(Duration::ZERO, "00:00:00,000")]
+    #[case(Duration::from_millis(1500), "00:00:01,500")]
+    #[case(Duration::from_secs(61), "00:01:01,000")]
+    #[case(Duration::from_secs(36 * 60 * 60 + 1), "36:00:01,000")]
+    fn test_format_srt_timestamp(#[case] input: Duration, #[case] expected: &str) {
+        assert_eq!(format_srt_timestamp(input), expected);
+    }
+
+    #[rstest]
+    #[case(Duration::from_secs(3), 3)]
+    #[case(Duration::from_millis(2500), 3)]
+    #[case(Duration::from_secs(3600), 3600)]
+    fn test_srt_cue_count(#[case] input: Duration, #[case] expected: u64) {
+        assert_eq!(srt_cue_count(input), expected);
+    }
+
+    #[rstest]
+    #[case(Duration::from_secs(5), false)]
+    #[case(Duration::from_secs(86_399), false)]
+    #[case(Duration::from_secs(86_400), false)]
+    #[case(Duration::from_millis(86_400_001), true)]
+    #[case(Duration::from_secs(86_401), true)]
+    #[case(Duration::from_secs(365 * 86_400), true)]
+    fn test_srt_cues_over_threshold(#[case] input: Duration, #[case] expected: bool) {
+        assert_eq!(srt_cues_over_threshold(input), expected);
+    }
+
+    fn srt_subtitles(input: Duration) -> String {
+        let mut subtitles = Vec::new();
+        write_srt_subtitles(&mut subtitles, input).unwrap();
+        String::from_utf8(subtitles).unwrap()
+    }
+
+    #[test]
+    fn test_srt_subtitles() {
+        let expected = "\
+1
+00:00:00,000 --> 00:00:01,000
+3
+
+2
+00:00:01,000 --> 00:00:02,000
+2
+
+3
+00:00:02,000 --> 00:00:03,000
+1
+
+";
+        assert_eq!(srt_subtitles(Duration::from_secs(3)), expected);
+    }
+
+    #[test]
+    fn test_srt_subtitles_over_a_minute() {
+        let subtitles = srt_subtitles(Duration::from_millis(61_500));
+        assert!(subtitles.starts_with("1\n00:00:00,000 --> 00:00:01,000\n1:01\n\n"));
+        assert!(subtitles.ends_with("62\n00:01:01,000 --> 00:01:01,500\n0\n\n"));
+    }
+
+    #[rstest]
+    #[case(60, 60, 60)]
+    #[case(90, 60, 60)]
+    #[case(30, 60, 30)]
+    #[case(1, 60, 1)]
+    #[case(0, 60, 0)]
+    #[case(60, 20, 20)]
+    #[case(30, 20, 10)]
+    #[case(1, 20, 1)]
+    fn test_second_bar(#[case] remaining_secs: u64, #[case] width: usize, #[case] filled: usize) {
+        let result = second_bar(remaining_secs, width);
+        assert_eq!(result.chars().count(), width);
+        assert_eq!(result.chars().filter(|c| *c == '█').count(), filled);
+        assert!(result.starts_with(&"█".repeat(filled)));
+    }
+
+    #[test]
+    fn test_queue_pinned_update() {
+        let mut output = Vec::new();
+        queue_pinned_update(&mut output, "\t     1:30", true).unwrap();
+        queue_pinned_update(&mut output, "\t     1:29", false).unwrap();
+        queue_pinned_update(&mut output, "\t     1:28", false).unwrap();
+        let expected = "\x1b[?25l\x1b7\t     1:30\
+                        \x1b[?25l\x1b8\t     1:29\
+                        \x1b[?25l\x1b8\t     1:28";
+        assert_eq!(String::from_utf8(output).unwrap(), expected);
+    }
+
+    #[rstest]
+    #[case(Duration::from_secs(100), Duration::from_secs(100), 0)]
+    #[case(Duration::from_millis(90_001), Duration::from_secs(100), 0)]
+    #[case(Duration::from_secs(90), Duration::from_secs(100), 1)]
+    #[case(Duration::from_secs(51), Duration::from_secs(100), 4)]
+    #[case(Duration::from_secs(50), Duration::from_secs(100), 5)]
+    #[case(Duration::from_secs(1), Duration::from_secs(100), 9)]
+    #[case(Duration::ZERO, Duration::from_secs(100), 10)]
+    #[case(Duration::from_secs(200), Duration::from_secs(100), 0)]
+    #[case(Duration::ZERO, Duration::ZERO, 10)]
+    fn test_progress_decile(
+        #[case] remaining: Duration,
+        #[case] total: Duration,
+        #[case] expected: u8,
+    ) {
+        assert_eq!(progress_decile(remaining, total), expected);
+    }
+
+    #[rstest]
+    #[case(0, 0, None)]
+    #[case(0, 1, Some(1))]
+    #[case(1, 1, None)]
+    #[case(1, 3, Some(3))]
+    #[case(9, 10, Some(10))]
+    #[case(5, 4, None)]
+    fn test_crossed_decile(#[case] last: u8, #[case] current: u8, #[case] expected: Option<u8>) {
+        assert_eq!(crossed_decile(last, current), expected);
+    }
+
+    #[test]
+    fn test_crossed_decile_sequence() {
+        let total = Duration::from_secs(180);
+        let mut last = 0;
+        let mut reported = Vec::new();
+        for elapsed in 0..=180 {
+            let remaining = total - Duration::from_secs(elapsed);
+            if let Some(decile) = crossed_decile(last, progress_decile(remaining, total)) {
+                reported.push(format_progress(decile, remaining));
+                last = decile;
+            }
+        }
+        assert_eq!(reported.len(), 10);
+        assert_eq!(reported[0], "10% (remaining 2:42)");
+        assert_eq!(reported[4], "50% (remaining 1:30)");
+        assert_eq!(reported[9], "100% (remaining 0)");
+    }
+
+    #[test]
+    fn test_pick_message_rotation() {
+        let lines = vec!["one".to_string(), "two".to_string(), "three".to_string()];
+        let result: Vec<Option<&str>> = (0..7).map(|tick| pick_message(&lines, tick)).collect();
+        let expected = [
+            Some("one"),
+            Some("two"),
+            Some("three"),
+            Some("one"),
+            Some("two"),
+            Some("three"),
+            Some("one"),
+        ];
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_pick_message_single() {
+        let lines = vec!["only".to_string()];
+        assert_eq!(pick_message(&lines, 0), Some("only"));
+        assert_eq!(pick_message(&lines, u64::MAX), Some("only"));
+    }
+
+    #[test]
+    fn test_pick_message_empty() {
+        assert_eq!(pick_message(&[], 0), None);
+        assert_eq!(pick_message(&[], 5), None);
+    }
+
+    #[rstest]
+    #[case(Duration::from_secs(600), None, true)]
+    #[case(Duration::from_secs(600), Some(Duration::from_secs(300)), false)]
+    #[case(Duration::from_millis(300_001), Some(Duration::from_secs(300)), false)]
+    #[case(Duration::from_secs(300), Some(Duration::from_secs(300)), false)]
+    #[case(Duration::from_millis(299_999), Some(Duration::from_secs(300)), true)]
+    #[case(Duration::from_secs(1), Some(Duration::from_secs(300)), true)]
+    fn test_should_show_remaining(
+        #[case] remaining: Duration,
+        #[case] show_under: Option<Duration>,
+        #[case] expected: bool,
+    ) {
+        assert_eq!(should_show_remaining(remaining, show_under), expected);
+    }
+
+    #[rstest]
+    #[case("https://example.com/ping", Ok("https://example.com/ping"))]
+    #[case(" http://localhost:8080 ", Ok("http://localhost:8080"))]
+    #[case("-o/tmp/x", Err("invalid ping URL: -o/tmp/x"))]
+    #[case("--config=/etc/passwd", Err("invalid ping URL: --config=/etc/passwd"))]
+    #[case("file:///etc/passwd", Err("invalid ping URL: file:///etc/passwd"))]
+    #[case("example.com", Err("invalid ping URL: example.com"))]
+    #[case("", Err("invalid ping URL: "))]
+    fn test_parse_ping_url(#[case] input: &str, #[case] expected: Result<&str, &str>) {
+        let expected = expected.map(String::from).map_err(String::from);
+        assert_eq!(parse_ping_url(input), expected);
+    }
+
+    #[rstest]
+    #[case(Some("https://example.com"), None, Some("https://example.com"))]
+    #[case(Some("https://example.com"), Some(2), None)]
+    #[case(Some("https://example.com"), Some(15), None)]
+    #[case(None, None, None)]
+    #[case(None, Some(2), None)]
+    fn test_ping_url_on_exit(
+        #[case] ping_url: Option<&str>,
+        #[case] close_signal: Option<i32>,
+        #[case] expected: Option<&str>,
+    ) {
+        assert_eq!(ping_url_on_exit(ping_url, close_signal), expected);
+    }
+
+    #[rstest]
+    #[case(1.0, Some(Duration::from_secs(1)))]
+    #[case(2.0, Some(Duration::from_millis(500)))]
+    #[case(10.0, Some(Duration::from_millis(100)))]
+    #[case(0.1, Some(Duration::from_secs(1)))]
+    #[case(60.0, Some(Duration::from_secs_f64(1.0 / 60.0)))]
+    #[case(1000.0, Some(Duration::from_secs_f64(1.0 / 60.0)))]
+    #[case(0.0, None)]
+    #[case(-10.0, None)]
+    #[case(f64::NAN, None)]
+    #[case(f64::INFINITY, None)]
+    fn test_fps_to_interval(#[case] fps: f64, #[case] expected: Option<Duration>) {
+        assert_eq!(fps_to_interval(fps), expected);
+    }
+
+    #[rstest]
+    #[case("10", Ok(Duration::from_millis(100)))]
+    #[case(" 1 ", Ok(Duration::from_secs(1)))]
+    #[case("0", Err("invalid frame rate: 0"))]
+    #[case("-5", Err("invalid frame rate: -5"))]
+    #[case("fast", Err("invalid frame rate: fast"))]
+    fn test_parse_fps(#[case] input: &str, #[case] expected: Result<Duration, &str>) {
+        assert_eq!(parse_fps(input), expected.map_err(String::from));
+    }
+
+    #[rstest]
+    #[case("1.0", Ok(1.0))]
+    #[case("0.5", Ok(0.5))]
+    #[case("60", Ok(60.0))]
+    #[case("0.0", Err("invalid display scale: 0.0"))]
+    #[case("-0.0", Err("invalid display scale: -0.0"))]
+    #[case("-0.5", Err("invalid display scale: -0.5"))]
+    #[case("NaN", Err("invalid display scale: NaN"))]
+    #[case("inf", Err("invalid display scale: inf"))]
+    #[case("big", Err("invalid display scale: big"))]
+    fn test_parse_display_scale(#[case] input: &str, #[case] expected: Result<f64, &str>) {
+        assert_eq!(parse_display_scale(input), expected.map_err(String::from));
+    }
+
+    #[rstest]
+    #[case(Duration::from_secs(90), 1.0, Duration::from_secs(90))]
+    #[case(Duration::from_secs(90), 2.0, Duration::from_secs(180))]
+    #[case(Duration::from_secs(90), 0.5, Duration::from_secs(45))]
+    #[case(Duration::from_secs(90), 0.0, Duration::ZERO)]
+    #[case(Duration::from_secs(90), f64::MAX, Duration::MAX)]
+    fn test_scale_remaining_time(
+        #[case] input: Duration,
+        #[case] factor: f64,
+        #[case] expected: Duration,
+    ) {
+        let result = scale_remaining_time(input, factor);
+        assert_eq!(result, expected);
+    }
 }
@@ -1,30 +1,59 @@
 use std::env;
-use std::io::{Write, stdin, stdout};
-use std::process::{ExitCode, Termination};
+use std::fs;
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, IsTerminal, Read, Write, stdin, stdout};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
 use std::string::String;
+use std::sync::{Arc, Mutex, PoisonError};
 use std::thread::{self, JoinHandle};
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use argh::FromArgs;
 use crossbeam_channel::{self, Receiver, Sender};
-use crossterm::style::Print;
-use crossterm::terminal::{Clear, ClearType};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::style::{Color, Print, ResetColor, SetForegroundColor};
+use crossterm::terminal::{self, Clear, ClearType};
 use crossterm::{ExecutableCommand, QueueableCommand, cursor};
-use nix::unistd;
+use nix::errno::Errno;
+use nix::sys::signal::kill;
+use nix::unistd::{self, Pid};
 use signal_hook::consts::signal;
 use signal_hook::iterator::{Handle, Signals};
 use signal_hook::low_level;
 
-use snooze::{format_remaining_time, sum_pause_args, wall_clock_end_time};
+use snooze::{
+    BusinessHours, CONFIRM_WORD, ConfirmGate, DangerPhase, DisplayMode, DoubleTap, LoopStep,
+    MENU_PRESETS, Menu, MenuAction, MenuKey, PrintTarget, Profile, RemainingTime,
+    SRT_CUES_WARNING_THRESHOLD, SnoozeExit, SnoozeResult, SnoozeStats, adjust_base_duration,
+    confirm_gate, crossed_decile, danger_phase, duration_until, duration_until_next_minute,
+    effective_refresh_interval, ends_timer_line, find_profile, format_compact_remaining_time,
+    format_done_message, format_progress, format_raw_seconds, format_remaining_time,
+    is_allowed_duration, is_confirmed, json_line, merge_extra_pause_args, next_loop_step,
+    parse_allowed_durations, parse_date_time, parse_display_scale, parse_duration, parse_fps,
+    parse_minute_of_hour, parse_ping_url, parse_profiles, parse_status_file, pick_message,
+    ping_url_on_exit, print_target, progress_decile, queue_pinned_update, remaining_seconds,
+    remaining_until, scale_remaining_time, second_bar, should_show_remaining, snooze_exit,
+    srt_cues_over_threshold, stats_report, status_file_conflict, status_file_contents,
+    sum_pause_args, unitless_seconds_over, wall_clock_end_time, wall_clock_midpoint_and_end_time,
+    wall_clock_target_time, write_srt_subtitles,
+};
+use time::{OffsetDateTime, UtcOffset};
 
 const REFRESH_TIME: Duration = Duration::from_secs(1);
+const SECOND_BAR_WIDTH: usize = 60;
+const EXTRA_ARGS_ENV: &str = "SNOOZE_EXTRA";
+const ALLOWED_ENV: &str = "SNOOZE_ALLOWED";
+const PING_TIMEOUT_SECONDS: &str = "5";
+const MESSAGE_INTERVAL: Duration = Duration::from_secs(60);
+const SIGINT_DOUBLE_TAP_WINDOW: Duration = Duration::from_secs(1);
 
 /** Pause for specified time.
 Like sleep, but show how much time is still left.
 Positional arguments specify how long to pause. They need not to be an integer.
 A number may be followed by a suffix: 's' for seconds (default if no suffix is
 provided), 'm' for minutes, 'h' for hours or 'd' for days. Multiple arguments
-are summed.
+are summed. Arguments from SNOOZE_EXTRA environment variable are added too.
 */
 #[derive(FromArgs)]
 #[argh(help_triggers("-h", "--help", "help"))]
@@ -37,13 +66,323 @@ struct SnoozeArgs {
     #[argh(switch, short = 't')]
     only_timer: bool,
 
+    /// instead of timer, print a line each time another 10% of time passes
+    #[argh(switch)]
+    quiet_progress: bool,
+
+    /// print --quiet-progress lines to stderr instead of stdout
+    #[argh(switch)]
+    progress_stderr: bool,
+
+    /// display wall-clock time when half of the time has passed
+    #[argh(switch)]
+    show_midpoint: bool,
+
+    /// warn when argument without unit suffix is longer than threshold
+    #[argh(switch)]
+    warn_unitless: bool,
+
+    /// threshold in seconds for --warn-unitless (default: 300)
+    #[argh(option, default = "300.0")]
+    unitless_threshold: f64,
+
+    /// multiply displayed remaining time by this factor (actual pause is not affected)
+    #[argh(option, default = "1.0", from_str_fn(parse_display_scale))]
+    display_scale: f64,
+
+    /// refresh display this many times per second (1 to 60, default: 1)
+    #[argh(option, from_str_fn(parse_fps))]
+    fps: Option<Duration>,
+
+    /// display a message from this file (one per line) below the timer
+    #[argh(option)]
+    message_file: Option<PathBuf>,
+
+    /// how often to change the message from --message-file (default: 1m)
+    #[argh(option, from_str_fn(parse_duration), default = "MESSAGE_INTERVAL")]
+    message_interval: Duration,
+
+    /// print remaining time once and update it in place only on SIGUSR1
+    #[argh(switch)]
+    pin: bool,
+
+    /// pause or resume on Ctrl+C; press it twice quickly to quit
+    #[argh(switch)]
+    sigint_pause: bool,
+
+    /// display remaining time as total number of seconds
+    #[argh(switch)]
+    raw_seconds_display: bool,
+
+    /// display remaining time as a bar of seconds during the last minute
+    #[argh(switch)]
+    second_bar: bool,
+
+    /// don't display remaining time until it drops below this threshold
+    #[argh(option, from_str_fn(parse_duration))]
+    show_under: Option<Duration>,
+
+    /// blink remaining time in red during final seconds of snooze
+    #[argh(option, from_str_fn(parse_duration))]
+    danger: Option<Duration>,
+
+    /// comma-separated list of allowed time intervals; defaults to
+    /// SNOOZE_ALLOWED environment variable
+    #[argh(option)]
+    allowed: Option<String>,
+
+    /// when interrupted by a signal, exit with status 3 instead of
+    /// terminating with that signal
+    #[argh(switch)]
+    clean_exit: bool,
+
+    /// send HTTP GET request to this URL when snooze finishes (requires curl)
+    #[argh(option, from_str_fn(parse_ping_url))]
+    ping_url: Option<String>,
+
+    /// write expected end time to this file, so it can be queried with --tmux
+    #[argh(option)]
+    status_file: Option<PathBuf>,
+
+    /// write remaining time to this file on SIGUSR1, instead of printing it
+    #[argh(option)]
+    snapshot_file: Option<PathBuf>,
+
+    /// write countdown as SRT subtitles file, with one cue per second
+    #[argh(option)]
+    srt: Option<PathBuf>,
+
+    /// record duration of each completed snooze in this file
+    #[argh(option)]
+    stats_file: Option<PathBuf>,
+
+    /// save power by refreshing display at most once per second, even
+    /// if higher --fps was requested
+    #[argh(switch)]
+    eco: bool,
+
+    /// print remaining time as JSON object in separate line on each update,
+    /// without any cursor control
+    #[argh(switch)]
+    json_line: bool,
+
+    /// refuse to start unless confirmed with --yes, or by typing START
+    /// in terminal
+    #[argh(switch)]
+    require_confirm: bool,
+
+    /// confirm start of snooze when --require-confirm is used
+    #[argh(switch)]
+    yes: bool,
+
+    /// format of remaining time, like "%H:%M:%S"; supported tokens are
+    /// %d, %H, %h, %M, %S, %s and %%, use %-M to skip zero padding
+    #[argh(option)]
+    time_format: Option<String>,
+
+    /// message to print after snooze is completed; URLs are displayed
+    /// as hyperlinks in supported terminals
+    #[argh(option)]
+    done_message: Option<String>,
+
+    /// read base time to pause from standard input; positional arguments
+    /// adjust it, like +30s or -1m (use -- before negative adjustments)
+    #[argh(switch)]
+    base_stdin: bool,
+
+    /// print statistics from --stats-file and exit
+    #[argh(switch)]
+    stats: bool,
+
+    /// print remaining time of snooze writing to given status file and exit
+    #[argh(option)]
+    tmux: Option<PathBuf>,
+
+    /// pause until specified date and time, like 2025-08-10T14:00:00
+    #[argh(option)]
+    until: Option<String>,
+
+    /// pause until the clock shows this minute of an hour, like :30
+    #[argh(option)]
+    until_next: Option<String>,
+
+    /// count down only within this daily time window, like 09:00-17:00
+    #[argh(option)]
+    business_hours: Option<BusinessHours>,
+
+    /// use defaults from named profile in config file
+    #[argh(option)]
+    profile: Option<String>,
+
+    /// choose time to pause from a list of presets
+    #[argh(switch)]
+    menu: bool,
+
     /// time to pause
     #[argh(positional, greedy)]
     number: Vec<String>,
 }
 
+struct DisplayOptions {
+    display_scale: f64,
+    raw_seconds: bool,
+    second_bar: bool,
+    pin: bool,
+    danger: Option<Duration>,
+    json_line: bool,
+    time_format: Option<String>,
+}
+
+impl DisplayOptions {
+    fn scale(&self, remaining: Duration) -> Duration {
+        scale_remaining_time(remaining, self.display_scale)
+    }
+
+    fn format_remaining(&self, remaining: Duration) -> String {
+        let remaining = self.scale(remaining);
+        if let Some(time_format) = &self.time_format {
+            return RemainingTime::from(remaining).format(time_format);
+        }
+        let remaining_secs = remaining_seconds(remaining);
+        if self.second_bar && remaining_secs < 60 {
+            return second_bar(remaining_secs, SECOND_BAR_WIDTH);
+        }
+        if self.raw_seconds {
+            return format_raw_seconds(remaining);
+        }
+        format_remaining_time(remaining)
+    }
+
+    fn colorize_remaining(&self, formatted: String, remaining: Duration) -> String {
+        match danger_phase(remaining, self.danger) {
+            DangerPhase::Highlighted => {
+                format!(
+                    "{}{formatted}{}",
+                    SetForegroundColor(Color::Red),
+                    ResetColor
+                )
+            }
+            DangerPhase::Normal | DangerPhase::Safe => formatted,
+        }
+    }
+}
+
+/// Point in time when snooze should finish. Relative durations use monotonic
+/// clock; absolute targets follow wall clock, even if it's adjusted. Business
+/// hours count only time within daily window since `start`. Paused snooze
+/// keeps remaining time, and continues as relative duration.
+#[derive(Clone, Copy)]
+enum Deadline {
+    Monotonic(Instant),
+    WallClock(OffsetDateTime),
+    BusinessHours {
+        hours: BusinessHours,
+        start: OffsetDateTime,
+        runtime: Duration,
+    },
+    Paused {
+        remaining: Duration,
+        hours: Option<(BusinessHours, UtcOffset)>,
+    },
+}
+
+impl Deadline {
+    fn remaining(self) -> Duration {
+        // now_local() fails once there are multiple threads, but offset
+        // doesn't matter when comparing two points in time
+        let now = OffsetDateTime::now_utc();
+        match self {
+            Self::Monotonic(end_time) => end_time - Instant::now(),
+            Self::WallClock(target) => remaining_until(target, now),
+            Self::BusinessHours {
+                hours,
+                start,
+                runtime,
+            } => hours.remaining(start, now, runtime),
+            Self::Paused { remaining, .. } => remaining,
+        }
+    }
+
+    fn toggle_pause(&mut self) {
+        *self = match *self {
+            Self::Paused {
+                remaining,
+                hours: None,
+            } => Self::Monotonic(Instant::now() + remaining),
+            Self::Paused {
+                remaining,
+                hours: Some((hours, offset)),
+            } => Self::BusinessHours {
+                hours,
+                start: OffsetDateTime::now_utc().to_offset(offset),
+                runtime: remaining,
+            },
+            Self::BusinessHours { hours, start, .. } => Self::Paused {
+                remaining: self.remaining(),
+                hours: Some((hours, start.offset())),
+            },
+            running => Self::Paused {
+                remaining: running.remaining(),
+                hours: None,
+            },
+        };
+    }
+}
+
+type SharedDeadline = Arc<Mutex<Deadline>>;
+
+fn deadline_remaining(deadline: &SharedDeadline) -> Duration {
+    deadline
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .remaining()
+}
+
+/// Messages displayed below the timer, rotated every `interval`. Rotation
+/// starts at random message.
+struct Messages {
+    lines: Vec<String>,
+    interval: Duration,
+    offset: u64,
+    start_time: Instant,
+}
+
+impl Messages {
+    fn load(path: Option<&Path>, interval: Duration) -> Self {
+        let lines = path
+            .and_then(|path| fs::read_to_string(path).ok())
+            .map(|contents| {
+                contents
+                    .lines()
+                    .map(str::trim)
+                    .filter(|line| !line.is_empty())
+                    .map(String::from)
+                    .collect()
+            })
+            .unwrap_or_default();
+        let offset = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since_epoch| u64::from(since_epoch.subsec_nanos()));
+        Self {
+            lines,
+            interval,
+            offset,
+            start_time: Instant::now(),
+        }
+    }
+
+    fn current(&self) -> Option<&str> {
+        let rotations = self.start_time.elapsed().as_nanos() / self.interval.as_nanos().max(1);
+        let tick = self
+            .offset
+            .wrapping_add(u64::try_from(rotations).unwrap_or(u64::MAX));
+        pick_message(&self.lines, tick)
+    }
+}
+
 enum SnoozeMessage {
     PrintTime,
+    PrintTimeOnDemand,
     Suspend,
     Terminate(i32),
 }
@@ -51,6 +390,8 @@ enum SnoozeMessage {
 fn install_signal_handlers(
     loop_sender: Sender<SnoozeMessage>,
     ui_sender: Sender<SnoozeMessage>,
+    deadline: SharedDeadline,
+    sigint_pause: bool,
 ) -> Option<(Handle, JoinHandle<()>)> {
     let known_signals = [
         signal::SIGUSR1,
@@ -63,11 +404,19 @@ fn install_signal_handlers(
     let mut signals = Signals::new(known_signals).ok()?;
     let handle = signals.handle();
     let thread = thread::spawn(move || {
+        let mut sigint_taps = DoubleTap::new(SIGINT_DOUBLE_TAP_WINDOW);
         for signalid in &mut signals {
             match signalid {
-                signal::SIGUSR1 => {
+                signal::SIGINT if sigint_pause && !sigint_taps.press(Instant::now()) => {
+                    deadline
+                        .lock()
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .toggle_pause();
                     let _ = ui_sender.send(SnoozeMessage::PrintTime);
                 }
+                signal::SIGUSR1 => {
+                    let _ = ui_sender.send(SnoozeMessage::PrintTimeOnDemand);
+                }
                 signal::SIGTSTP => {
                     let _ = ui_sender.send(SnoozeMessage::Suspend);
                     let _ = loop_sender.send(SnoozeMessage::Suspend);
@@ -84,11 +433,214 @@ fn install_signal_handlers(
 }
 
 fn is_foreground() -> bool {
+    // stdin may be a pipe, e.g. with --base-stdin
     unistd::tcgetpgrp(stdin())
+        .or_else(|_| unistd::tcgetpgrp(stdout()))
         .ok()
         .is_some_and(|pid| pid == unistd::getpgrp())
 }
 
+/// Reads base time to pause from stdin. `extra_args` from environment
+/// variable are added, like any other unsigned adjustment.
+fn read_base_stdin(adjustments: &[String], extra_args: &[&str]) -> Result<Duration, String> {
+    let mut base = String::new();
+    stdin()
+        .read_to_string(&mut base)
+        .map_err(|err| format!("Couldn't read standard input: {err}"))?;
+    let adjustments: Vec<&str> = adjustments
+        .iter()
+        .map(String::as_str)
+        .chain(extra_args.iter().copied())
+        .collect();
+    adjust_base_duration(&base, &adjustments)
+}
+
+fn prompt_confirmation() -> std::io::Result<bool> {
+    print!("Type {CONFIRM_WORD} to begin snooze: ");
+    stdout().flush()?;
+    let mut input = String::new();
+    stdin().read_line(&mut input)?;
+    Ok(is_confirmed(&input))
+}
+
+fn until_target(until: &str) -> Result<(OffsetDateTime, Duration), &'static str> {
+    let now = OffsetDateTime::now_local().unwrap_or_else(|_| OffsetDateTime::now_utc());
+    let target = parse_date_time(until, now.offset()).ok_or("Invalid date and time supplied")?;
+    let runtime = duration_until(now, target).ok_or("Date and time supplied is in the past")?;
+    Ok((target, runtime))
+}
+
+fn until_next_target(until_next: &str) -> Result<(OffsetDateTime, Duration), &'static str> {
+    let now = OffsetDateTime::now_local().unwrap_or_else(|_| OffsetDateTime::now_utc());
+    let minute = parse_minute_of_hour(until_next).ok_or("Invalid minute supplied")?;
+    let runtime = duration_until_next_minute(now, minute);
+    Ok((now + runtime, runtime))
+}
+
+fn config_file_path() -> Option<PathBuf> {
+    let config_dir = env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".config")))?;
+    Some(config_dir.join("snooze").join("config"))
+}
+
+fn load_profile(name: &str) -> Result<Profile, String> {
+    let path = config_file_path().ok_or("Couldn't find config file")?;
+    let contents = fs::read_to_string(&path)
+        .map_err(|err| format!("Couldn't read config file {}: {err}", path.display()))?;
+    let profiles = parse_profiles(&contents)
+        .map_err(|err| format!("Invalid config file {}: {err}", path.display()))?;
+    find_profile(profiles, name)
+}
+
+fn is_running(pid: i32) -> bool {
+    matches!(kill(Pid::from_raw(pid), None), Ok(()) | Err(Errno::EPERM))
+}
+
+fn query_status_file(path: &Path) -> Option<String> {
+    let contents = fs::read_to_string(path).ok()?;
+    let (pid, end) = parse_status_file(&contents)?;
+    if !is_running(pid) {
+        return None;
+    }
+    let remaining = remaining_until(end, OffsetDateTime::now_utc());
+    Some(format_compact_remaining_time(remaining))
+}
+
+fn write_file_atomically(path: &Path, contents: &str) -> std::io::Result<()> {
+    let mut tmp_path = path.as_os_str().to_owned();
+    tmp_path.push(".tmp");
+    fs::write(&tmp_path, contents)?;
+    fs::rename(&tmp_path, path)
+}
+
+fn write_srt_file(path: &Path, runtime: Duration) -> std::io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    write_srt_subtitles(&mut writer, runtime)?;
+    writer.flush()
+}
+
+fn print_stats(path: &Path) -> SnoozeResult {
+    match stats_report(fs::read_to_string(path)) {
+        Ok(report) => {
+            println!("{report}");
+            SnoozeResult::Good
+        }
+        Err(err) => {
+            println!("Couldn't read stats file: {err}");
+            SnoozeResult::OsError
+        }
+    }
+}
+
+fn append_stats_record(path: &Path, runtime: Duration) -> std::io::Result<()> {
+    let record = SnoozeStats::record(OffsetDateTime::now_utc(), runtime);
+    // single write of a short line is atomic enough when appending
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?
+        .write_all(record.as_bytes())
+}
+
+fn ping(url: &str) {
+    let _ = Command::new("curl")
+        .args(["--silent", "--output", "/dev/null"])
+        .args(["--max-time", PING_TIMEOUT_SECONDS])
+        .arg("--url")
+        .arg(url)
+        .stdin(Stdio::null())
+        .status();
+}
+
+fn draw_menu(items: &[&str], selected: usize, redraw: bool) -> std::io::Result<()> {
+    let mut stdout = stdout();
+    if redraw {
+        stdout.queue(cursor::MoveToPreviousLine(
+            u16::try_from(items.len()).unwrap_or(0),
+        ))?;
+    }
+    for (idx, item) in items.iter().enumerate() {
+        let marker = if idx == selected { '>' } else { ' ' };
+        stdout
+            .queue(Clear(ClearType::CurrentLine))?
+            .queue(Print(format!("{marker} {item}\r\n")))?;
+    }
+    stdout.flush()
+}
+
+fn read_menu_key() -> std::io::Result<MenuKey> {
+    loop {
+        let Event::Key(KeyEvent {
+            code,
+            modifiers,
+            kind: KeyEventKind::Press,
+            ..
+        }) = event::read()?
+        else {
+            continue;
+        };
+        match code {
+            KeyCode::Up | KeyCode::Char('k') => return Ok(MenuKey::Up),
+            KeyCode::Down | KeyCode::Char('j') => return Ok(MenuKey::Down),
+            KeyCode::Enter => return Ok(MenuKey::Select),
+            KeyCode::Esc | KeyCode::Char('q') => return Ok(MenuKey::Cancel),
+            KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => {
+                return Ok(MenuKey::Cancel);
+            }
+            _ => (),
+        }
+    }
+}
+
+fn menu_loop(items: &[&str]) -> std::io::Result<Option<usize>> {
+    let mut menu = Menu::new(items.len());
+    draw_menu(items, menu.selected(), false)?;
+    loop {
+        match menu.handle_key(read_menu_key()?) {
+            MenuAction::Continue => draw_menu(items, menu.selected(), true)?,
+            MenuAction::Select(idx) => return Ok(Some(idx)),
+            MenuAction::Cancel => return Ok(None),
+        }
+    }
+}
+
+/// Disables raw mode and shows cursor again, on every exit path from menu.
+struct MenuTerminalGuard;
+
+impl Drop for MenuTerminalGuard {
+    fn drop(&mut self) {
+        let _ = terminal::disable_raw_mode();
+        let _ = stdout().execute(cursor::Show);
+    }
+}
+
+fn run_menu(items: &[&str]) -> std::io::Result<Option<usize>> {
+    let mut stdout = stdout();
+    let result = {
+        terminal::enable_raw_mode()?;
+        let _guard = MenuTerminalGuard;
+        stdout.execute(cursor::Hide)?;
+        menu_loop(items)
+    };
+    stdout
+        .queue(cursor::MoveToPreviousLine(
+            u16::try_from(items.len()).unwrap_or(0),
+        ))?
+        .queue(Clear(ClearType::FromCursorDown))?
+        .flush()?;
+    result
+}
+
+fn print_progress(line: &str, to_stderr: bool) {
+    if to_stderr {
+        eprintln!("{line}");
+    } else {
+        println!("{line}");
+    }
+}
+
 fn print_remaining_time(msg: &str) -> std::io::Result<()> {
     let mut stdout = stdout();
     stdout
@@ -100,67 +652,250 @@ fn print_remaining_time(msg: &str) -> std::io::Result<()> {
     Ok(())
 }
 
+fn print_json_line(line: &str) -> std::io::Result<()> {
+    let mut stdout = stdout().lock();
+    writeln!(stdout, "{line}")?;
+    stdout.flush()
+}
+
+/// Prints `message` in the line below the timer, and moves cursor back.
+/// First message is printed with a newline, so terminal scrolls if needed.
+fn print_message(message: &str, first: bool) -> std::io::Result<()> {
+    let mut stdout = stdout();
+    if first {
+        stdout.queue(Print("\n"))?;
+    } else {
+        stdout.queue(cursor::MoveToNextLine(1))?;
+    }
+    stdout
+        .queue(Clear(ClearType::CurrentLine))?
+        .queue(Print(message))?
+        .queue(cursor::MoveToPreviousLine(1))?
+        .flush()?;
+    Ok(())
+}
+
 fn start_ui(
-    end_time: Instant,
+    deadline: SharedDeadline,
     formatted_end_time: String,
+    display_options: DisplayOptions,
+    messages: Messages,
+    snapshot_file: Option<PathBuf>,
+    clean_exit: bool,
     ui_receiver: Receiver<SnoozeMessage>,
 ) -> JoinHandle<()> {
     let mut stdout = stdout();
     thread::spawn(move || {
         let mut did_print = false;
-        let mut clean_exit = true;
+        let mut did_print_message = false;
+        let mut end_line = true;
+        let mut snapshot_failed = false;
         loop {
             match ui_receiver.recv() {
                 Ok(SnoozeMessage::Terminate(signal)) => {
-                    clean_exit = signal == 0;
+                    end_line = ends_timer_line(signal, clean_exit);
                     break;
                 }
-                Ok(SnoozeMessage::Suspend) => {
+                Ok(SnoozeMessage::Suspend) if !display_options.json_line => {
                     let _ = stdout.execute(cursor::Show);
                 }
-                Ok(SnoozeMessage::PrintTime) => {
+                Ok(SnoozeMessage::Suspend) => (),
+                Ok(msg @ (SnoozeMessage::PrintTime | SnoozeMessage::PrintTimeOnDemand)) => {
+                    let on_demand = matches!(msg, SnoozeMessage::PrintTimeOnDemand);
+                    if let (PrintTarget::SnapshotFile, Some(path)) = (
+                        print_target(on_demand, snapshot_file.is_some()),
+                        &snapshot_file,
+                    ) {
+                        let remaining = display_options.scale(deadline_remaining(&deadline));
+                        let contents = format!("{}\n", format_compact_remaining_time(remaining));
+                        if let Err(err) = write_file_atomically(path, &contents)
+                            && !snapshot_failed
+                        {
+                            eprintln!("Couldn't write snapshot file: {err}");
+                            snapshot_failed = true;
+                        }
+                        continue;
+                    }
+
+                    if display_options.json_line {
+                        let remaining = display_options.scale(deadline_remaining(&deadline));
+                        let _ = print_json_line(&json_line(remaining));
+                        continue;
+                    }
+
                     if !is_foreground() {
                         continue;
                     }
 
-                    let remaining = end_time - Instant::now();
-                    let formatted_remaining = format_remaining_time(remaining);
+                    let remaining = deadline_remaining(&deadline);
+                    let formatted_remaining = display_options
+                        .colorize_remaining(display_options.format_remaining(remaining), remaining);
                     let msg = format!("\t{formatted_remaining}\t{formatted_end_time}");
-                    if print_remaining_time(msg.as_str()).is_ok() {
+                    let printed = if display_options.pin {
+                        queue_pinned_update(&mut stdout, &msg, !did_print)
+                            .and_then(|()| stdout.flush())
+                    } else {
+                        print_remaining_time(msg.as_str())
+                    };
+                    if printed.is_ok() {
                         did_print = true;
                     }
+                    if let Some(message) = messages.current().filter(|_| !display_options.pin)
+                        && print_message(message, !did_print_message).is_ok()
+                    {
+                        did_print_message = true;
+                    }
                 }
                 Err(_) => break,
             }
         }
-        if clean_exit && did_print && is_foreground() {
+        if end_line && did_print && is_foreground() {
             println!();
+            if did_print_message {
+                println!();
+            }
+        }
+        if !display_options.json_line {
+            let _ = stdout.execute(cursor::Show);
         }
-        let _ = stdout.execute(cursor::Show);
     })
 }
 
-#[repr(u8)]
-pub enum SnoozeResult {
-    Good = 0,
-    UserError = 1,
-    OsError = 2,
-}
+fn main() -> SnoozeResult {
+    let mut start_time = Instant::now();
 
-impl Termination for SnoozeResult {
-    fn report(self) -> ExitCode {
-        ExitCode::from(self as u8)
+    let parsed_args: SnoozeArgs = argh::from_env();
+
+    if let Some(status_file) = parsed_args.tmux {
+        println!("{}", query_status_file(&status_file).unwrap_or_default());
+        return SnoozeResult::Good;
     }
-}
 
-fn main() -> SnoozeResult {
-    let start_time = Instant::now();
+    if parsed_args.stats {
+        let Some(stats_file) = &parsed_args.stats_file else {
+            println!("--stats requires --stats-file");
+            println!("Run snooze --help for more information.");
+            return SnoozeResult::UserError;
+        };
+        return print_stats(stats_file);
+    }
 
-    let parsed_args: SnoozeArgs = argh::from_env();
+    if parsed_args.json_line && parsed_args.pin {
+        println!("--json-line can't be used together with --pin");
+        println!("Run snooze --help for more information.");
+        return SnoozeResult::UserError;
+    }
+    if parsed_args.json_line && parsed_args.done_message.is_some() {
+        println!("--json-line can't be used together with --done-message");
+        println!("Run snooze --help for more information.");
+        return SnoozeResult::UserError;
+    }
 
-    let num_args: Vec<&str> = parsed_args.number.iter().map(String::as_str).collect();
-    let Some(desired_runtime) = sum_pause_args(&num_args) else {
-        if parsed_args.number.is_empty() {
+    let has_target = parsed_args.until.is_some() || parsed_args.until_next.is_some();
+    if parsed_args.until.is_some() && parsed_args.until_next.is_some() {
+        println!("--until can't be used together with --until-next");
+        println!("Run snooze --help for more information.");
+        return SnoozeResult::UserError;
+    }
+    if has_target && (parsed_args.menu || !parsed_args.number.is_empty()) {
+        println!("--until and --until-next can't be used together with time to pause");
+        println!("Run snooze --help for more information.");
+        return SnoozeResult::UserError;
+    }
+    if parsed_args.base_stdin && (has_target || parsed_args.menu) {
+        println!("--base-stdin can't be used together with --until, --until-next or --menu");
+        println!("Run snooze --help for more information.");
+        return SnoozeResult::UserError;
+    }
+    if has_target && parsed_args.business_hours.is_some() {
+        println!("--until and --until-next can't be used together with --business-hours");
+        println!("Run snooze --help for more information.");
+        return SnoozeResult::UserError;
+    }
+    if parsed_args.status_file.is_some()
+        && let Some(option) = status_file_conflict(
+            parsed_args.business_hours.is_some(),
+            parsed_args.sigint_pause,
+        )
+    {
+        println!("--status-file can't be used together with {option}");
+        println!("Run snooze --help for more information.");
+        return SnoozeResult::UserError;
+    }
+    let until = match (&parsed_args.until, &parsed_args.until_next) {
+        (Some(until), _) => Some(until_target(until)),
+        (None, Some(until_next)) => Some(until_next_target(until_next)),
+        (None, None) => None,
+    };
+    let until = match until {
+        None => None,
+        Some(Ok(until)) => Some(until),
+        Some(Err(msg)) => {
+            println!("{msg}");
+            println!("Run snooze --help for more information.");
+            return SnoozeResult::UserError;
+        }
+    };
+
+    let profile = match parsed_args.profile.as_deref().map(load_profile) {
+        None => None,
+        Some(Ok(profile)) => Some(profile),
+        Some(Err(msg)) => {
+            println!("{msg}");
+            return SnoozeResult::UserError;
+        }
+    };
+
+    let extra_args = env::var(EXTRA_ARGS_ENV).unwrap_or_default();
+    let cli_args: Vec<&str> = if parsed_args.base_stdin {
+        Vec::new()
+    } else if parsed_args.menu {
+        match run_menu(&MENU_PRESETS) {
+            Ok(Some(idx)) => vec![MENU_PRESETS[idx]],
+            Ok(None) => return SnoozeResult::Good,
+            Err(err) => {
+                println!("Couldn't display menu: {err}");
+                return SnoozeResult::OsError;
+            }
+        }
+    } else {
+        let explicit_args: Vec<&str> = parsed_args.number.iter().map(String::as_str).collect();
+        match &profile {
+            Some(profile) => profile.duration_args(&explicit_args, until.is_some()),
+            None => explicit_args,
+        }
+    };
+    let num_args = match merge_extra_pause_args(&cli_args, &extra_args) {
+        Ok(num_args) => num_args,
+        Err(fragment) => {
+            println!("Invalid time interval supplied in {EXTRA_ARGS_ENV}: {fragment}");
+            println!("Run snooze --help for more information.");
+            return SnoozeResult::UserError;
+        }
+    };
+    if until.is_some() && !extra_args.trim().is_empty() {
+        println!("{EXTRA_ARGS_ENV} can't be used together with --until or --until-next");
+        println!("Run snooze --help for more information.");
+        return SnoozeResult::UserError;
+    }
+    let base = match parsed_args
+        .base_stdin
+        .then(|| read_base_stdin(&parsed_args.number, &num_args))
+    {
+        None => None,
+        Some(Ok(base)) => Some(base),
+        Some(Err(msg)) => {
+            println!("{msg}");
+            println!("Run snooze --help for more information.");
+            return SnoozeResult::UserError;
+        }
+    };
+    let Some(desired_runtime) = until
+        .map(|(_, runtime)| runtime)
+        .or(base)
+        .or_else(|| sum_pause_args(&num_args))
+    else {
+        if num_args.is_empty() {
             println!("Missing mandatory arguments");
         } else {
             println!("Invalid time interval supplied");
@@ -169,32 +904,170 @@ fn main() -> SnoozeResult {
         return SnoozeResult::UserError;
     };
 
-    let end_time = start_time + desired_runtime;
-    let formatted_end_time = (!parsed_args.only_timer)
-        .then(|| wall_clock_end_time(desired_runtime))
+    if parsed_args.warn_unitless
+        && let Some((arg, seconds)) = num_args.iter().find_map(|arg| {
+            unitless_seconds_over(arg, parsed_args.unitless_threshold).map(|secs| (arg, secs))
+        })
+    {
+        eprintln!(
+            "Note: {} is interpreted as seconds ({:.1} minutes); use suffix like {}m for minutes",
+            arg.trim(),
+            seconds / 60.0,
+            arg.trim()
+        );
+    }
+
+    if let Some(allowed) = parsed_args.allowed.or_else(|| env::var(ALLOWED_ENV).ok()) {
+        let allowed_durations = match parse_allowed_durations(&allowed) {
+            Ok(allowed_durations) => allowed_durations,
+            Err(item) => {
+                println!("Invalid allowed time interval supplied: {item}");
+                println!("Run snooze --help for more information.");
+                return SnoozeResult::UserError;
+            }
+        };
+        if !is_allowed_duration(desired_runtime, &allowed_durations) {
+            println!("Time interval not allowed. Allowed values: {allowed}");
+            return SnoozeResult::UserError;
+        }
+    }
+
+    let refresh_time =
+        effective_refresh_interval(parsed_args.fps.unwrap_or(REFRESH_TIME), parsed_args.eco);
+
+    match confirm_gate(
+        parsed_args.require_confirm,
+        parsed_args.yes,
+        stdin().is_terminal(),
+    ) {
+        ConfirmGate::Proceed => (),
+        ConfirmGate::Prompt => match prompt_confirmation() {
+            // time spent waiting for confirmation doesn't count
+            Ok(true) => start_time = Instant::now(),
+            Ok(false) => {
+                println!("Snooze not confirmed");
+                return SnoozeResult::UserError;
+            }
+            Err(err) => {
+                println!("Couldn't read confirmation: {err}");
+                return SnoozeResult::OsError;
+            }
+        },
+        ConfirmGate::Refuse => {
+            println!("Snooze requires confirmation; use --yes when not running in terminal");
+            println!("Run snooze --help for more information.");
+            return SnoozeResult::UserError;
+        }
+    }
+
+    // target doesn't move while waiting for confirmation, so time to pause
+    // gets shorter
+    let desired_runtime = match until {
+        Some((target, _)) => remaining_until(target, OffsetDateTime::now_utc()),
+        None => desired_runtime,
+    };
+
+    let deadline = Arc::new(Mutex::new(match (until, parsed_args.business_hours) {
+        (Some((target, _)), _) => Deadline::WallClock(target),
+        (None, Some(hours)) => Deadline::BusinessHours {
+            hours,
+            start: OffsetDateTime::now_local().unwrap_or_else(|_| OffsetDateTime::now_utc()),
+            runtime: desired_runtime,
+        },
+        (None, None) => Deadline::Monotonic(start_time + desired_runtime),
+    }));
+    // end time can't be easily predicted when counting only business hours
+    let formatted_end_time = (!parsed_args.only_timer && parsed_args.business_hours.is_none())
+        .then(|| match until {
+            _ if parsed_args.show_midpoint => wall_clock_midpoint_and_end_time(desired_runtime),
+            Some((target, _)) => wall_clock_target_time(target),
+            None => wall_clock_end_time(desired_runtime),
+        })
         .flatten()
         .unwrap_or_default();
 
-    let short_sleep = REFRESH_TIME > desired_runtime;
+    let short_sleep = refresh_time > desired_runtime;
     let invoked_as_sleep = env::current_exe()
         .ok()
         .and_then(|p| p.file_name().map(|fname| fname == "sleep"))
         .unwrap_or(false);
-    let quiet_mode = parsed_args.quiet || short_sleep || invoked_as_sleep;
+    let quiet_mode =
+        parsed_args.quiet || parsed_args.quiet_progress || short_sleep || invoked_as_sleep;
+
+    if let Some(srt_file) = &parsed_args.srt {
+        if srt_cues_over_threshold(desired_runtime) {
+            println!(
+                "Warning: subtitles file will contain more than {SRT_CUES_WARNING_THRESHOLD} cues"
+            );
+        }
+        if let Err(err) = write_srt_file(srt_file, desired_runtime) {
+            println!("Couldn't write subtitles file: {err}");
+            return SnoozeResult::OsError;
+        }
+    }
+
+    if let Some(status_file) = &parsed_args.status_file {
+        let contents =
+            status_file_contents(unistd::getpid().as_raw(), desired_runtime).unwrap_or_default();
+        if let Err(err) = write_file_atomically(status_file, &contents) {
+            println!("Couldn't write status file: {err}");
+            return SnoozeResult::OsError;
+        }
+    }
 
     let (loop_sender, loop_receiver) = crossbeam_channel::unbounded();
     let (ui_sender, ui_receiver) = crossbeam_channel::unbounded();
 
-    let Some((signals_handle, signals_thread)) =
-        install_signal_handlers(loop_sender, ui_sender.clone())
-    else {
+    let Some((signals_handle, signals_thread)) = install_signal_handlers(
+        loop_sender,
+        ui_sender.clone(),
+        Arc::clone(&deadline),
+        parsed_args.sigint_pause,
+    ) else {
         println!("Couldn't create signal handlers");
         return SnoozeResult::OsError;
     };
 
-    let ui_thread = start_ui(end_time, formatted_end_time, ui_receiver);
+    let explicit_display = parsed_args.raw_seconds_display || parsed_args.second_bar;
+    let profile_display = profile
+        .as_ref()
+        .and_then(|profile| profile.display_mode(explicit_display));
+    let display_options = DisplayOptions {
+        display_scale: parsed_args.display_scale,
+        raw_seconds: parsed_args.raw_seconds_display
+            || profile_display == Some(DisplayMode::RawSeconds),
+        second_bar: parsed_args.second_bar || profile_display == Some(DisplayMode::SecondBar),
+        pin: parsed_args.pin,
+        danger: match &profile {
+            Some(profile) => profile.danger(parsed_args.danger),
+            None => parsed_args.danger,
+        },
+        json_line: parsed_args.json_line,
+        time_format: match &profile {
+            Some(profile) => profile
+                .time_format(parsed_args.time_format.as_deref(), explicit_display)
+                .map(String::from),
+            None => parsed_args.time_format,
+        },
+    };
+    let ui_thread = start_ui(
+        Arc::clone(&deadline),
+        formatted_end_time,
+        display_options,
+        Messages::load(
+            parsed_args.message_file.as_deref(),
+            parsed_args.message_interval,
+        ),
+        parsed_args.snapshot_file,
+        parsed_args.clean_exit,
+        ui_receiver,
+    );
+    if parsed_args.pin && !quiet_mode {
+        let _ = ui_sender.send(SnoozeMessage::PrintTime);
+    }
 
     let mut close_signal: Option<i32> = None;
+    let mut last_decile = 0;
 
     loop {
         match loop_receiver.try_recv() {
@@ -207,23 +1080,62 @@ fn main() -> SnoozeResult {
             }
             Ok(_) | Err(_) => (),
         }
-        let remaining = end_time - Instant::now();
-        if remaining.is_zero() {
-            break;
+        let remaining = deadline_remaining(&deadline);
+        if parsed_args.quiet_progress
+            && let Some(decile) =
+                crossed_decile(last_decile, progress_decile(remaining, desired_runtime))
+        {
+            print_progress(
+                &format_progress(
+                    decile,
+                    scale_remaining_time(remaining, parsed_args.display_scale),
+                ),
+                parsed_args.progress_stderr,
+            );
+            last_decile = decile;
         }
-        if !quiet_mode {
+        let LoopStep::Sleep(sleep_time) = next_loop_step(remaining, refresh_time) else {
+            break;
+        };
+        if !quiet_mode
+            && !parsed_args.pin
+            && should_show_remaining(remaining, parsed_args.show_under)
+        {
             let _ = ui_sender.try_send(SnoozeMessage::PrintTime);
         }
-        thread::sleep(remaining.min(REFRESH_TIME));
+        thread::sleep(sleep_time);
     }
 
     let _ = ui_sender.send(SnoozeMessage::Terminate(close_signal.unwrap_or(0)));
     signals_handle.close();
     let _ = ui_thread.join();
     let _ = signals_thread.join();
-    if let Some(signal_) = close_signal {
-        let _ = low_level::emulate_default_handler(signal_);
+    if let Some(status_file) = &parsed_args.status_file {
+        let _ = fs::remove_file(status_file);
+    }
+    if let Some(message) = &parsed_args.done_message
+        && close_signal.is_none()
+    {
+        println!("{}", format_done_message(message, stdout().is_terminal()));
+    }
+    if let Some(stats_file) = &parsed_args.stats_file
+        && close_signal.is_none()
+        && let Err(err) = append_stats_record(stats_file, desired_runtime)
+    {
+        eprintln!("Couldn't write stats file: {err}");
+    }
+    let ping_url = match &profile {
+        Some(profile) => profile.ping_url(parsed_args.ping_url.as_deref()),
+        None => parsed_args.ping_url.as_deref(),
+    };
+    if let Some(url) = ping_url_on_exit(ping_url, close_signal) {
+        ping(url);
+    }
+    match snooze_exit(close_signal, parsed_args.clean_exit) {
+        SnoozeExit::Result(result) => result,
+        SnoozeExit::Signal(signal_) => {
+            let _ = low_level::emulate_default_handler(signal_);
+            SnoozeResult::Good
+        }
     }
-
-    SnoozeResult::Good
 }